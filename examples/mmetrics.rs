@@ -0,0 +1,25 @@
+use anyhow::Result;
+use concurrency::{multiply_with, AmapMetrics, Matrix, MultiplyOptions, MULTIPLY_METRICS};
+
+// 执行命令 cargo run --example mmetrics
+fn main() -> Result<()> {
+    //multiply 的指标 key 是固定的, 直接用 MULTIPLY_METRICS 初始化
+    let metrics = AmapMetrics::new(&MULTIPLY_METRICS);
+
+    let n = 64;
+    let a = Matrix::new((0..(n * n) as i64).collect::<Vec<_>>(), n, n);
+    let b = Matrix::new((0..(n * n) as i64).rev().collect::<Vec<_>>(), n, n);
+
+    let options = MultiplyOptions::new()
+        .metrics(metrics.clone())
+        .progress(|p| {
+            //每完成 10% 打印一次
+            if p.done % (p.total / 10).max(1) == 0 {
+                println!("progress: {}/{}", p.done, p.total);
+            }
+        });
+    let _c = multiply_with(&a, &b, options)?;
+
+    println!("{}", metrics);
+    Ok(())
+}
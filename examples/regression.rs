@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Result};
+use concurrency::{LinearRegression, Matrix, Vector};
+use std::{env, fs};
+
+// 没有传入文件时使用 assets/regression.csv: y = 3 + 2 * x1 - x2 加上一点噪声
+const SAMPLE: &str = include_str!("../assets/regression.csv");
+
+// 执行命令 cargo run --example regression -- path/to/data.csv
+// csv 需要有表头, 所有行都能解析成数字的列才会被使用, 至少需要 2 个这样的列
+// 最后一个数字列作为 y, 其他的作为特征, 非数字的列会被忽略
+// 注意 assets/juventus.csv 只有一个数字列 (Kit Number), 不能直接用来拟合
+fn main() -> Result<()> {
+    let content = match env::args().nth(1) {
+        Some(path) => fs::read_to_string(path)?,
+        None => SAMPLE.to_string(),
+    };
+    let (names, x, y) = parse_csv(&content)?;
+
+    let model = LinearRegression::fit(&x, &y)?;
+    println!("y = {}", names.last().unwrap());
+    println!("intercept: {:.4}", model.intercept);
+    for (name, c) in names.iter().zip(&model.coefficients) {
+        println!("{}: {:.4}", name, c);
+    }
+    println!("R^2: {:.4}", model.r2(&x, &y)?);
+    Ok(())
+}
+
+// 返回 (列名, 特征矩阵, y)
+fn parse_csv(content: &str) -> Result<(Vec<String>, Matrix<f64>, Vector<f64>)> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let header = split_line(lines.next().ok_or_else(|| anyhow!("empty csv"))?);
+    let rows = lines.map(split_line).collect::<Vec<_>>();
+
+    //每一行都能解析成数字的列才使用
+    let numeric = (0..header.len())
+        .filter(|&j| {
+            rows.iter()
+                .all(|r| r.get(j).is_some_and(|v| v.trim().parse::<f64>().is_ok()))
+        })
+        .collect::<Vec<_>>();
+    if numeric.len() < 2 {
+        return Err(anyhow!("need at least 2 numeric columns"));
+    }
+    let (features, target) = numeric.split_at(numeric.len() - 1);
+
+    let mut x = Vec::with_capacity(rows.len() * features.len());
+    let mut y = Vec::with_capacity(rows.len());
+    for r in &rows {
+        for &j in features {
+            x.push(r[j].trim().parse()?);
+        }
+        y.push(r[target[0]].trim().parse()?);
+    }
+    let names = numeric.iter().map(|&j| header[j].clone()).collect();
+    Ok((
+        names,
+        Matrix::new(x, rows.len(), features.len()),
+        Vector::new(y),
+    ))
+}
+
+// 按逗号切分, 双引号中的逗号不切分
+fn split_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use concurrency::{
+    dot, multiply_with, par_dot_product, Accumulator, Matrix, MultiplyOptions, Plain, Simd,
+};
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+const N: usize = 100_000;
+const ROUNDS: u32 = 1000;
+
+// 执行命令 cargo run --release --example simd
+// debug 模式下标量循环没有优化, 对比结果没有参考意义
+fn main() -> Result<()> {
+    let mut rng = rand::thread_rng();
+    let a: Vec<f32> = (0..N).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let b: Vec<f32> = (0..N).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+    let plain = bench(|| Plain.dot(&a, &b))?;
+    let simd = bench(|| Simd.dot(&a, &b))?;
+    println!("dot_product f32 x {}", N);
+    println!("  plain: {:?}", plain);
+    println!("  simd:  {:?} ({:.2}x)", simd, ratio(plain, simd));
+
+    let a: Vec<i32> = (0..N).map(|_| rng.gen_range(-100..100)).collect();
+    let b: Vec<i32> = (0..N).map(|_| rng.gen_range(-100..100)).collect();
+    let plain = bench(|| Plain.dot(&a, &b))?;
+    let simd = bench(|| Simd.dot(&a, &b))?;
+    println!("dot_product i32 x {}", N);
+    println!("  plain: {:?}", plain);
+    println!("  simd:  {:?} ({:.2}x)", simd, ratio(plain, simd));
+
+    // multiply 中每个单元格都要经过 channel, 点乘只占一部分耗时
+    let n = 256;
+    let a = Matrix::new((0..n * n).map(|_| rng.gen()).collect::<Vec<f32>>(), n, n);
+    let b = Matrix::new((0..n * n).map(|_| rng.gen()).collect::<Vec<f32>>(), n, n);
+    let start = Instant::now();
+    multiply_with(&a, &b, MultiplyOptions::new())?;
+    let plain = start.elapsed();
+    let start = Instant::now();
+    multiply_with(&a, &b, MultiplyOptions::new().accumulator(Simd))?;
+    let simd = start.elapsed();
+    println!("multiply f32 {}x{}", n, n);
+    println!("  plain: {:?}", plain);
+    println!("  simd:  {:?} ({:.2}x)", simd, ratio(plain, simd));
+
+    // 很长的向量, 数据放不进 cache, 多线程分块计算
+    let len = 20_000_000;
+    let a = (0..len).map(|_| rng.gen()).collect::<Vec<f64>>();
+    let b = (0..len).map(|_| rng.gen()).collect::<Vec<f64>>();
+    let start = Instant::now();
+    std::hint::black_box(dot(&a, &b)?);
+    let single = start.elapsed();
+    let start = Instant::now();
+    std::hint::black_box(par_dot_product(&a, &b)?);
+    let par = start.elapsed();
+    println!("dot_product f64 x {}", len);
+    println!("  single: {:?}", single);
+    println!("  par:    {:?} ({:.2}x)", par, ratio(single, par));
+
+    Ok(())
+}
+
+// 跑 ROUNDS 次取平均耗时, N 取得比较小, 数据可以放在 cache 里, 避免测出来的是内存带宽
+fn bench<T, E>(f: impl Fn() -> Result<T, E>) -> Result<Duration, E> {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        std::hint::black_box(f()?);
+    }
+    Ok(start.elapsed() / ROUNDS)
+}
+
+fn ratio(base: Duration, other: Duration) -> f64 {
+    base.as_secs_f64() / other.as_secs_f64()
+}
@@ -0,0 +1,296 @@
+use crate::{vector::check_len, ConcurrencyError, Result, Scalar, VectorView};
+
+// dot_product 的累加策略, 决定 a[i] * b[i] 的乘法和累加怎么做
+// Output 可以和 T 不一样, 比如 Widening 把 i32 累加到 i64
+// a, b 长度不一致时返回 LengthMismatch, 不会只算较短的部分
+pub trait Accumulator<T> {
+    type Output;
+
+    fn dot(&self, a: &[T], b: &[T]) -> Result<Self::Output>;
+
+    // 视图版本, 带 stride 的视图 (比如矩阵的一列) 也可以传进来
+    // 默认实现把不连续的视图复制成连续的再调用 dot, 内置的策略都直接按 stride 读取, 不会复制
+    fn dot_view(&self, a: VectorView<'_, T>, b: VectorView<'_, T>) -> Result<Self::Output>
+    where
+        T: Copy,
+    {
+        check_len(&a, &b, "Accumulator dot")?;
+        match (a.as_slice(), b.as_slice()) {
+            (Some(a), Some(b)) => self.dot(a, b),
+            _ => self.dot(&a.to_vector(), &b.to_vector()),
+        }
+    }
+
+    // 是否需要连续的切片才能发挥作用, 比如 Simd 只有连续的切片才能向量化
+    // 返回 true 时 multiply 先把 b 整体转置一次, 每一列都以连续切片的视图传入, 而不是带 stride 的视图
+    fn prefers_contiguous(&self) -> bool {
+        false
+    }
+}
+
+// 引用也可以作为累加策略, 多个 worker 可以共享同一个策略, 不需要 clone
+impl<T, A: Accumulator<T> + ?Sized> Accumulator<T> for &A {
+    type Output = A::Output;
+
+    fn dot(&self, a: &[T], b: &[T]) -> Result<Self::Output> {
+        (**self).dot(a, b)
+    }
+
+    fn dot_view(&self, a: VectorView<'_, T>, b: VectorView<'_, T>) -> Result<Self::Output>
+    where
+        T: Copy,
+    {
+        (**self).dot_view(a, b)
+    }
+
+    fn prefers_contiguous(&self) -> bool {
+        (**self).prefers_contiguous()
+    }
+}
+
+// 默认策略, 直接使用 * 和 +=, 整数溢出时 debug 下 panic, release 下回绕
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plain;
+
+// 溢出时返回错误, 错误信息中带上溢出的位置
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checked;
+
+// 溢出时回绕, debug 和 release 的行为一致
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wrapping;
+
+// 溢出时取类型的最大/最小值
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Saturating;
+
+// 先转换成更宽的类型再相乘累加, 比如 i32 -> i64, 宽类型中仍然溢出时返回错误
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Widening;
+
+impl<T: Scalar> Accumulator<T> for Plain {
+    type Output = T;
+
+    fn dot(&self, a: &[T], b: &[T]) -> Result<T> {
+        self.dot_view(VectorView::new(a), VectorView::new(b))
+    }
+
+    fn dot_view(&self, a: VectorView<'_, T>, b: VectorView<'_, T>) -> Result<T> {
+        check_len(&a, &b, "Plain dot")?;
+        let mut sum = T::zero();
+        for (x, y) in a.iter().zip(b.iter()) {
+            sum += *x * *y;
+        }
+        Ok(sum)
+    }
+}
+
+fn overflow(index: usize) -> ConcurrencyError {
+    ConcurrencyError::Overflow { index }
+}
+
+macro_rules! impl_int_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulator<$t> for Checked {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Checked dot")?;
+                    let mut sum: $t = 0;
+                    for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+                        sum = x
+                            .checked_mul(*y)
+                            .and_then(|v| sum.checked_add(v))
+                            .ok_or_else(|| overflow(i))?;
+                    }
+                    Ok(sum)
+                }
+            }
+
+            impl Accumulator<$t> for Wrapping {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Wrapping dot")?;
+                    let mut sum: $t = 0;
+                    for (x, y) in a.iter().zip(b.iter()) {
+                        sum = sum.wrapping_add(x.wrapping_mul(*y));
+                    }
+                    Ok(sum)
+                }
+            }
+
+            impl Accumulator<$t> for Saturating {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Saturating dot")?;
+                    let mut sum: $t = 0;
+                    for (x, y) in a.iter().zip(b.iter()) {
+                        sum = sum.saturating_add(x.saturating_mul(*y));
+                    }
+                    Ok(sum)
+                }
+            }
+        )*
+    };
+}
+
+impl_int_accumulator!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_widening {
+    ($($t:ty => $w:ty),*) => {
+        $(
+            impl Accumulator<$t> for Widening {
+                type Output = $w;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$w> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$w> {
+                    check_len(&a, &b, "Widening dot")?;
+                    let mut sum: $w = 0;
+                    for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+                        // 宽类型中乘法不会溢出, 只需要检查加法
+                        sum = sum
+                            .checked_add(*x as $w * *y as $w)
+                            .ok_or_else(|| overflow(i))?;
+                    }
+                    Ok(sum)
+                }
+            }
+        )*
+    };
+}
+
+impl_widening!(
+    i8 => i16, i16 => i32, i32 => i64, i64 => i128,
+    u8 => u16, u16 => u32, u32 => u64, u64 => u128
+);
+
+// Kahan 补偿求和, 用一个补偿变量记录每次相加丢失的低位
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Kahan;
+
+// Neumaier 改进的 Kahan 求和, 新加的项比累加值还大时也能保留精度
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Neumaier;
+
+// 两两分治求和, 误差从 O(n) 降到 O(log n), 不需要额外的内存
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pairwise;
+
+// 使用 mul_add 做融合乘加, 乘法和加法之间只做一次舍入
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fma;
+
+// 小于这个长度就直接循环累加, 避免递归太深
+const PAIRWISE_BLOCK: usize = 8;
+
+macro_rules! impl_float_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulator<$t> for Kahan {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Kahan dot")?;
+                    let mut sum: $t = 0.0;
+                    let mut c: $t = 0.0;
+                    for (x, y) in a.iter().zip(b.iter()) {
+                        let y = *x * *y - c;
+                        let t = sum + y;
+                        c = (t - sum) - y;
+                        sum = t;
+                    }
+                    Ok(sum)
+                }
+            }
+
+            impl Accumulator<$t> for Neumaier {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Neumaier dot")?;
+                    let mut sum: $t = 0.0;
+                    let mut c: $t = 0.0;
+                    for (x, y) in a.iter().zip(b.iter()) {
+                        let x = *x * *y;
+                        let t = sum + x;
+                        if sum.abs() >= x.abs() {
+                            c += (sum - t) + x;
+                        } else {
+                            c += (x - t) + sum;
+                        }
+                        sum = t;
+                    }
+                    Ok(sum + c)
+                }
+            }
+
+            impl Accumulator<$t> for Pairwise {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Pairwise dot")?;
+                    if a.len() <= PAIRWISE_BLOCK {
+                        let mut sum: $t = 0.0;
+                        for (x, y) in a.iter().zip(b.iter()) {
+                            sum += *x * *y;
+                        }
+                        return Ok(sum);
+                    }
+                    let mid = a.len() / 2;
+                    let (a0, a1) = a.split_at(mid);
+                    let (b0, b1) = b.split_at(mid);
+                    Ok(self.dot_view(a0, b0)? + self.dot_view(a1, b1)?)
+                }
+            }
+
+            impl Accumulator<$t> for Fma {
+                type Output = $t;
+
+                fn dot(&self, a: &[$t], b: &[$t]) -> Result<$t> {
+                    self.dot_view(VectorView::new(a), VectorView::new(b))
+                }
+
+                fn dot_view(&self, a: VectorView<'_, $t>, b: VectorView<'_, $t>) -> Result<$t> {
+                    check_len(&a, &b, "Fma dot")?;
+                    let mut sum: $t = 0.0;
+                    for (x, y) in a.iter().zip(b.iter()) {
+                        sum = x.mul_add(*y, sum);
+                    }
+                    Ok(sum)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_accumulator!(f32, f64);
@@ -0,0 +1,188 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{pool, ConcurrencyError, Matrix, Result, Vector};
+
+// 参与广播运算的操作数, 每种操作数都看成一个二维的形状 (row, col):
+// - Matrix: (row, col)
+// - Row: 行向量 (1, n), Vector 默认按行向量处理, 和 NumPy 中一维数组的规则一致
+// - Col: 列向量 (n, 1)
+// - Scalar: (1, 1)
+//
+// 广播规则(和 NumPy 一致), 两个形状逐个维度比较:
+// 1. 两个维度相等, 结果就是这个维度
+// 2. 其中一个维度为 1, 这个维度上的数据被重复使用, 结果取另一个维度
+// 3. 否则形状不兼容, 返回错误
+// 比如 (2, 3) 和 Row (1, 3) 得到 (2, 3), (2, 3) 和 Col (2, 1) 得到 (2, 3), Row (1, 3) 和 Col (2, 1) 得到 (2, 3)
+#[derive(Debug, Clone, Copy)]
+pub enum Broadcast<'a, T> {
+    Matrix(&'a Matrix<T>),
+    Row(&'a [T]),
+    Col(&'a [T]),
+    Scalar(T),
+}
+
+impl<'a, T: Copy> Broadcast<'a, T> {
+    pub fn shape(&self) -> (usize, usize) {
+        match self {
+            Broadcast::Matrix(m) => (m.row(), m.col()),
+            Broadcast::Row(v) => (1, v.len()),
+            Broadcast::Col(v) => (v.len(), 1),
+            Broadcast::Scalar(_) => (1, 1),
+        }
+    }
+
+    // i, j 是结果矩阵中的位置, 长度为 1 的维度上总是取第 0 个
+    fn at(&self, i: usize, j: usize) -> T {
+        match self {
+            Broadcast::Matrix(m) => {
+                let i = if m.row() == 1 { 0 } else { i };
+                let j = if m.col() == 1 { 0 } else { j };
+                m.data()[i * m.col() + j]
+            }
+            Broadcast::Row(v) => v[if v.len() == 1 { 0 } else { j }],
+            Broadcast::Col(v) => v[if v.len() == 1 { 0 } else { i }],
+            Broadcast::Scalar(v) => *v,
+        }
+    }
+}
+
+impl<'a, T> From<&'a Matrix<T>> for Broadcast<'a, T> {
+    fn from(m: &'a Matrix<T>) -> Self {
+        Broadcast::Matrix(m)
+    }
+}
+
+impl<'a, T> From<&'a Vector<T>> for Broadcast<'a, T> {
+    fn from(v: &'a Vector<T>) -> Self {
+        Broadcast::Row(v)
+    }
+}
+
+impl<T> Vector<T> {
+    //作为行向量 (1, n) 参与广播
+    pub fn as_row(&self) -> Broadcast<'_, T> {
+        Broadcast::Row(self)
+    }
+
+    //作为列向量 (n, 1) 参与广播
+    pub fn as_col(&self) -> Broadcast<'_, T> {
+        Broadcast::Col(self)
+    }
+}
+
+// 按上面的规则计算结果形状, 然后对结果中的每个位置执行 f(a[i][j], b[i][j])
+// 结果按行切分, 在 pool 的 worker 中并行填充
+pub fn broadcast<T, U, V, F>(a: Broadcast<T>, b: Broadcast<U>, f: F) -> Result<Matrix<V>>
+where
+    T: Copy + Sync,
+    U: Copy + Sync,
+    V: Send,
+    F: Fn(T, U) -> V + Sync,
+{
+    let (sa, sb) = (a.shape(), b.shape());
+    let row = broadcast_dim(sa, sb, sa.0, sb.0)?;
+    let col = broadcast_dim(sa, sb, sa.1, sb.1)?;
+    let chunks = pool::run(pool::split(row, col), |rows| {
+        let mut data = Vec::with_capacity(rows.len() * col);
+        for i in rows {
+            for j in 0..col {
+                data.push(f(a.at(i, j), b.at(i, j)));
+            }
+        }
+        data
+    })?;
+    Ok(Matrix::new(
+        chunks.into_iter().flatten().collect::<Vec<_>>(),
+        row,
+        col,
+    ))
+}
+
+fn broadcast_dim(sa: (usize, usize), sb: (usize, usize), a: usize, b: usize) -> Result<usize> {
+    match (a, b) {
+        _ if a == b => Ok(a),
+        (1, _) => Ok(b),
+        (_, 1) => Ok(a),
+        _ => Err(ConcurrencyError::ShapeMismatch {
+            op: "Broadcast",
+            left: sa,
+            right: sb,
+        }),
+    }
+}
+
+// 常用的四则运算, rhs 可以是 &Matrix, &Vector(行向量), v.as_col() 或者 Broadcast::Scalar(x)
+impl<T: Copy + Send + Sync> Matrix<T> {
+    pub fn broadcast_add<'a>(&'a self, rhs: impl Into<Broadcast<'a, T>>) -> Result<Matrix<T>>
+    where
+        T: Add<Output = T>,
+    {
+        broadcast(self.into(), rhs.into(), |a, b| a + b)
+    }
+
+    pub fn broadcast_sub<'a>(&'a self, rhs: impl Into<Broadcast<'a, T>>) -> Result<Matrix<T>>
+    where
+        T: Sub<Output = T>,
+    {
+        broadcast(self.into(), rhs.into(), |a, b| a - b)
+    }
+
+    pub fn broadcast_mul<'a>(&'a self, rhs: impl Into<Broadcast<'a, T>>) -> Result<Matrix<T>>
+    where
+        T: Mul<Output = T>,
+    {
+        broadcast(self.into(), rhs.into(), |a, b| a * b)
+    }
+
+    pub fn broadcast_div<'a>(&'a self, rhs: impl Into<Broadcast<'a, T>>) -> Result<Matrix<T>>
+    where
+        T: Div<Output = T>,
+    {
+        broadcast(self.into(), rhs.into(), |a, b| a / b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_row_col_scalar() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let bias = Vector::new([10, 20, 30]);
+        assert_eq!(a.broadcast_add(&bias)?.data(), [11, 22, 33, 14, 25, 36]);
+
+        let scale = Vector::new([1, 10]);
+        assert_eq!(
+            a.broadcast_mul(scale.as_col())?.data(),
+            [1, 2, 3, 40, 50, 60]
+        );
+        assert_eq!(
+            a.broadcast_sub(Broadcast::Scalar(1))?.data(),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        //行向量和列向量广播成 (2, 3)
+        let outer = broadcast(scale.as_col(), bias.as_row(), |x, y| x * y)?;
+        assert_eq!((outer.row(), outer.col()), (2, 3));
+        assert_eq!(outer.data(), [10, 20, 30, 100, 200, 300]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_broadcast_incompatible() {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let v = Vector::new([1, 2]);
+        let err = a.broadcast_add(&v).unwrap_err();
+        assert_eq!(
+            err,
+            ConcurrencyError::ShapeMismatch {
+                op: "Broadcast",
+                left: (2, 3),
+                right: (1, 2)
+            }
+        );
+        let b = Matrix::new([1, 2, 3, 4], 2, 2);
+        assert!(a.broadcast_div(&b).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+};
+
+use crate::{dot_product, One, Result, Scalar, Vector, Zero};
+
+// 复数 re + im*i, 满足 Scalar 约束, 可以直接作为 Vector / Matrix 的元素参与 dot_product 和 multiply
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Complex<T> {
+    //共轭复数 re - im*i
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Complex<T> {
+    //模的平方 re^2 + im^2, 不需要开方
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Complex<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.re += rhs.re;
+        self.im += rhs.im;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl<T> Mul for Complex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+// (a + bi) / (c + di) = ((ac + bd) + (bc - ad)i) / (c^2 + d^2)
+impl<T> Div for Complex<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let d = rhs.norm_sqr();
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / d,
+            (self.im * rhs.re - self.re * rhs.im) / d,
+        )
+    }
+}
+
+impl<T: Zero> Zero for Complex<T> {
+    fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+}
+
+impl<T> One for Complex<T>
+where
+    T: Copy + Zero + One + Sub<Output = T>,
+{
+    fn one() -> Self {
+        Self::new(T::one(), T::zero())
+    }
+}
+
+// 显示成 1+2i / 1-2i, 精度等格式参数会传给实部和虚部, 比如 {:.2} 显示成 1.00+2.00i
+impl<T> fmt::Display for Complex<T>
+where
+    T: fmt::Display + Copy + PartialOrd + Zero + Neg<Output = T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.re, f)?;
+        if self.im < T::zero() {
+            write!(f, "-")?;
+            fmt::Display::fmt(&-self.im, f)?;
+        } else if self.im == T::zero() {
+            //-0.0 不小于 0, 但直接显示会变成 1+-0i, 当成 0 处理
+            write!(f, "+")?;
+            fmt::Display::fmt(&T::zero(), f)?;
+        } else {
+            write!(f, "+")?;
+            fmt::Display::fmt(&self.im, f)?;
+        }
+        write!(f, "i")
+    }
+}
+
+// 复数向量的内积 (Hermitian dot), 先对 a 取共轭再点乘: sum(conj(a[i]) * b[i])
+// 这样 hdot(a, a) 就是 a 的模的平方, 虚部为 0
+pub fn hdot<T>(a: Vector<Complex<T>>, b: Vector<Complex<T>>) -> Result<Complex<T>>
+where
+    T: Copy + Neg<Output = T>,
+    Complex<T>: Scalar,
+{
+    let a = Vector::new(a.iter().map(|v| v.conj()).collect::<Vec<_>>());
+    dot_product(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Matrix;
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let a = Complex::new(1, 2);
+        let b = Complex::new(3, -1);
+        assert_eq!(a + b, Complex::new(4, 1));
+        assert_eq!(a - b, Complex::new(-2, 3));
+        assert_eq!(a * b, Complex::new(5, 5));
+        assert_eq!(a.conj(), Complex::new(1, -2));
+        assert_eq!(
+            Complex::new(5.0, 5.0) / Complex::new(3.0, -1.0),
+            Complex::new(1.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_complex_display() {
+        assert_eq!(Complex::new(1, 2).to_string(), "1+2i");
+        assert_eq!(Complex::new(1, -2).to_string(), "1-2i");
+        assert_eq!(format!("{:.1}", Complex::new(0.5, -1.25)), "0.5-1.2i");
+        assert_eq!(Complex::new(1.0, -0.0).to_string(), "1+0i");
+        assert_eq!(format!("{:.1}", Complex::new(1.0, -0.0)), "1.0+0.0i");
+    }
+
+    #[test]
+    fn test_complex_dot_and_multiply() -> Result<()> {
+        let a = || Vector::new([Complex::new(1, 2), Complex::new(3, 4)]);
+        let b = || Vector::new([Complex::new(5, 6), Complex::new(7, 8)]);
+        assert_eq!(dot_product(a(), b())?, Complex::new(-18, 68));
+        assert_eq!(hdot(a(), b())?, Complex::new(70, -8));
+        assert_eq!(hdot(a(), a())?, Complex::new(30, 0));
+
+        // [[1+i, 2], [0, 1-i]] * 单位矩阵
+        let m = Matrix::new(
+            [
+                Complex::new(1, 1),
+                Complex::new(2, 0),
+                Complex::new(0, 0),
+                Complex::new(1, -1),
+            ],
+            2,
+            2,
+        );
+        let c = m * Matrix::identity(2);
+        assert_eq!(c.to_string(), "[1+1i 2+0i]\n[0+0i 1-1i]");
+        assert_eq!(c.conj_transpose().to_string(), "[1-1i 0+0i]\n[2+0i 1+1i]");
+        Ok(())
+    }
+}
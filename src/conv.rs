@@ -0,0 +1,238 @@
+use crate::{structure::fill, ConcurrencyError, Matrix, Result, Scalar};
+
+// 卷积时边界外的数据怎么取
+// 除 Valid 外, 上下左右按 kernel 大小的一半补齐, stride 为 1 时输出和输入一样大
+// 以一行 a b c d, 每边补 2 个为例:
+// - Valid: 不补齐, 输出变小
+// - Zero: 0 0 | a b c d | 0 0
+// - Reflect: c b | a b c d | c b (不重复边界元素, 和 NumPy 的 reflect 一致)
+// - Replicate: a a | a b c d | d d
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    Valid,
+    Zero,
+    Reflect,
+    Replicate,
+}
+
+impl Padding {
+    //kernel 长度为 k 时, 前面和后面各补多少个
+    fn pad(&self, k: usize) -> (usize, usize) {
+        match self {
+            Padding::Valid => (0, 0),
+            _ => ((k - 1) / 2, k - 1 - (k - 1) / 2),
+        }
+    }
+
+    //补齐后的下标 i 对应原数据中的下标, Zero 越界时返回 None
+    fn map(&self, i: isize, n: usize) -> Option<usize> {
+        let n = n as isize;
+        if (0..n).contains(&i) {
+            return Some(i as usize);
+        }
+        match self {
+            Padding::Valid | Padding::Zero => None,
+            Padding::Replicate => Some(i.clamp(0, n - 1) as usize),
+            Padding::Reflect => {
+                if n == 1 {
+                    return Some(0);
+                }
+                //kernel 比数据还大时可能需要来回反射多次, 按周期 2(n-1) 折叠
+                let period = 2 * (n - 1);
+                let i = i.rem_euclid(period);
+                Some(if i < n { i } else { period - i } as usize)
+            }
+        }
+    }
+}
+
+// 一个维度上的输出长度和起始偏移
+fn out_len(n: usize, k: usize, padding: Padding, stride: usize) -> Result<(usize, isize)> {
+    let (before, after) = padding.pad(k);
+    let padded = n + before + after;
+    if k == 0 || padded < k {
+        return Err(ConcurrencyError::invalid(
+            "Matrix convolve",
+            format!("kernel size {} larger than input {}", k, padded),
+        ));
+    }
+    Ok(((padded - k) / stride + 1, -(before as isize)))
+}
+
+impl<T: Scalar> Matrix<T> {
+    //二维卷积, kernel 会先上下左右翻转(数学上的卷积), 不翻转的版本见 correlate2d
+    pub fn convolve2d(
+        &self,
+        kernel: &Matrix<T>,
+        padding: Padding,
+        stride: usize,
+    ) -> Result<Matrix<T>> {
+        self.correlate2d(&flip(kernel), padding, stride)
+    }
+
+    //二维互相关, out[i][j] = sum(in[i*stride + u][j*stride + v] * kernel[u][v])
+    //输出按行切分成若干段, 每段交给一个 worker 计算, 和 multiply 的 fan-out 方式一致
+    pub fn correlate2d(
+        &self,
+        kernel: &Matrix<T>,
+        padding: Padding,
+        stride: usize,
+    ) -> Result<Matrix<T>> {
+        if stride == 0 {
+            return Err(ConcurrencyError::invalid(
+                "Matrix convolve",
+                "stride must be positive",
+            ));
+        }
+        let (kr, kc) = (kernel.row(), kernel.col());
+        let (out_row, off_r) = out_len(self.row(), kr, padding, stride)?;
+        let (out_col, off_c) = out_len(self.col(), kc, padding, stride)?;
+        let (data, col) = (self.data(), self.col());
+        fill(out_row, out_col, |i, out| {
+            for j in 0..out_col {
+                let mut sum = T::zero();
+                for u in 0..kr {
+                    let Some(r) = padding.map((i * stride + u) as isize + off_r, self.row()) else {
+                        continue;
+                    };
+                    for v in 0..kc {
+                        if let Some(c) = padding.map((j * stride + v) as isize + off_c, col) {
+                            sum += data[r * col + c] * kernel.data()[u * kc + v];
+                        }
+                    }
+                }
+                out.push(sum);
+            }
+        })
+    }
+
+    //可分离 kernel 的快速卷积, kernel = col_kernel(列向量) * row_kernel(行向量)
+    //先按行做一维卷积, 再按列做一维卷积, 计算量从 kr*kc 降到 kr+kc
+    //结果和用完整 kernel 调用 convolve2d 一致
+    pub fn convolve2d_separable(
+        &self,
+        col_kernel: &[T],
+        row_kernel: &[T],
+        padding: Padding,
+        stride: usize,
+    ) -> Result<Matrix<T>> {
+        if stride == 0 {
+            return Err(ConcurrencyError::invalid(
+                "Matrix convolve",
+                "stride must be positive",
+            ));
+        }
+        let (kr, kc) = (col_kernel.len(), row_kernel.len());
+        let (out_row, off_r) = out_len(self.row(), kr, padding, stride)?;
+        let (out_col, off_c) = out_len(self.col(), kc, padding, stride)?;
+        let (data, col) = (self.data(), self.col());
+
+        //第一遍: 每一行在列方向上卷积, 得到 row * out_col 的中间结果
+        let tmp = fill(self.row(), out_col, |r, out| {
+            for j in 0..out_col {
+                let mut sum = T::zero();
+                for v in 0..kc {
+                    if let Some(c) = padding.map((j * stride + v) as isize + off_c, col) {
+                        sum += data[r * col + c] * row_kernel[kc - 1 - v];
+                    }
+                }
+                out.push(sum);
+            }
+        })?;
+
+        //第二遍: 中间结果在行方向上卷积
+        let tmp_data = tmp.data();
+        fill(out_row, out_col, |i, out| {
+            for j in 0..out_col {
+                let mut sum = T::zero();
+                for u in 0..kr {
+                    if let Some(r) = padding.map((i * stride + u) as isize + off_r, self.row()) {
+                        sum += tmp_data[r * out_col + j] * col_kernel[kr - 1 - u];
+                    }
+                }
+                out.push(sum);
+            }
+        })
+    }
+}
+
+// 上下左右翻转 kernel
+fn flip<T: Copy>(kernel: &Matrix<T>) -> Matrix<T> {
+    let data = kernel.data().iter().rev().copied().collect::<Vec<_>>();
+    Matrix::new(data, kernel.row(), kernel.col())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convolve2d_padding() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3);
+        let ones = Matrix::new([1; 9], 3, 3);
+
+        let valid = a.convolve2d(&ones, Padding::Valid, 1)?;
+        assert_eq!(valid.data(), [45]);
+
+        let zero = a.convolve2d(&ones, Padding::Zero, 1)?;
+        assert_eq!(zero.data(), [12, 21, 16, 27, 45, 33, 24, 39, 28]);
+
+        //左上角: 补齐后为 [[5,4,5],[2,1,2],[5,4,5]]
+        let reflect = a.convolve2d(&ones, Padding::Reflect, 1)?;
+        assert_eq!(reflect.data()[0], 33);
+
+        //左上角: 补齐后为 [[1,1,2],[1,1,2],[4,4,5]]
+        let replicate = a.convolve2d(&ones, Padding::Replicate, 1)?;
+        assert_eq!(replicate.data()[0], 21);
+
+        let strided = a.convolve2d(&ones, Padding::Zero, 2)?;
+        assert_eq!(strided.data(), [12, 16, 24, 28]);
+        assert!(a.convolve2d(&ones, Padding::Zero, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convolve_flips_kernel() -> Result<()> {
+        let a = Matrix::new([1, 2, 3], 1, 3);
+        let k = Matrix::new([1, 0, -1], 1, 3);
+        assert_eq!(a.correlate2d(&k, Padding::Valid, 1)?.data(), [-2]);
+        assert_eq!(a.convolve2d(&k, Padding::Valid, 1)?.data(), [2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_separable_matches_full() -> Result<()> {
+        let (row, col) = (40, 300);
+        let a = Matrix::new(
+            (0..row * col).map(|v| (v % 17) as i64).collect::<Vec<_>>(),
+            row,
+            col,
+        );
+        let ck = [1, 2, 1];
+        let rk = [1, 0, -1, 2];
+        let full = Matrix::new(
+            ck.iter()
+                .flat_map(|c| rk.iter().map(move |r| c * r))
+                .collect::<Vec<_>>(),
+            3,
+            4,
+        );
+        for padding in [
+            Padding::Valid,
+            Padding::Zero,
+            Padding::Reflect,
+            Padding::Replicate,
+        ] {
+            for stride in [1, 2] {
+                let expected = a.convolve2d(&full, padding, stride)?;
+                let actual = a.convolve2d_separable(&ck, &rk, padding, stride)?;
+                assert_eq!(
+                    (actual.row(), actual.col()),
+                    (expected.row(), expected.col())
+                );
+                assert_eq!(actual.data(), expected.data());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use std::{error::Error, fmt};
+
+// crate 中所有公开 API 返回的错误, 调用方可以 match 具体的失败原因
+// 实现了 std::error::Error, 在 anyhow 的代码里可以直接用 ? 转换
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConcurrencyError {
+    // 两个矩阵的形状不满足运算的要求, 比如 multiply 时 a.col != b.row, 形状为 (row, col)
+    ShapeMismatch {
+        op: &'static str,
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    // 只有方阵才能做的运算, 比如 pow, trace, 图的邻接矩阵
+    NotSquare {
+        op: &'static str,
+        shape: (usize, usize),
+    },
+    // 两个向量的长度不一致, left / right 是两边的长度
+    LengthMismatch {
+        op: &'static str,
+        left: usize,
+        right: usize,
+    },
+    // 下标越界
+    OutOfBounds {
+        op: &'static str,
+        index: usize,
+        len: usize,
+    },
+    // 其他不合法的参数, 比如 stride 为 0, 空向量, q 不在 [0, 1] 之间
+    InvalidArgument {
+        op: &'static str,
+        reason: String,
+    },
+    // 整数点乘在 index 处溢出
+    Overflow {
+        index: usize,
+    },
+    // multiply 中某个单元格的点乘出错, source 是具体的原因
+    Cell {
+        row: usize,
+        col: usize,
+        source: Box<ConcurrencyError>,
+    },
+    // 最小二乘的矩阵不满秩
+    RankDeficient {
+        column: usize,
+    },
+    // 最短路径中存在负权环
+    NegativeCycle {
+        node: usize,
+    },
+    // AmapMetrics 中没有注册的 key
+    UnknownMetric(String),
+    // worker 线程 panic 或者没有返回结果, idx 是 worker 的编号
+    WorkerFailed {
+        idx: usize,
+    },
+    // 发送任务时 worker 的 channel 已经关闭
+    ChannelClosed,
+}
+
+pub type Result<T, E = ConcurrencyError> = std::result::Result<T, E>;
+
+impl ConcurrencyError {
+    pub(crate) fn invalid(op: &'static str, reason: impl Into<String>) -> Self {
+        Self::InvalidArgument {
+            op,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConcurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShapeMismatch { op, left, right } => write!(
+                f,
+                "{} error: shape {}x{} does not match {}x{}",
+                op, left.0, left.1, right.0, right.1
+            ),
+            Self::NotSquare { op, shape } => write!(
+                f,
+                "{} error: expected a square matrix, got {}x{}",
+                op, shape.0, shape.1
+            ),
+            Self::LengthMismatch { op, left, right } => {
+                write!(f, "{} error: length {} != {}", op, left, right)
+            }
+            Self::OutOfBounds { op, index, len } => write!(
+                f,
+                "{} error: index {} out of bounds for len {}",
+                op, index, len
+            ),
+            Self::InvalidArgument { op, reason } => write!(f, "{} error: {}", op, reason),
+            Self::Overflow { index } => write!(f, "Dot product overflow at index {}", index),
+            Self::Cell { row, col, source } => write!(
+                f,
+                "Matrix multiply error at cell ({}, {}): {}",
+                row, col, source
+            ),
+            Self::RankDeficient { column } => {
+                write!(f, "Matrix lstsq error: rank deficient at column {}", column)
+            }
+            Self::NegativeCycle { node } => {
+                write!(f, "Graph error: negative cycle through node {}", node)
+            }
+            Self::UnknownMetric(key) => write!(f, "Metric error: key {} not found", key),
+            Self::WorkerFailed { idx } => write!(f, "Worker {} failed", idx),
+            Self::ChannelClosed => write!(f, "Worker channel closed"),
+        }
+    }
+}
+
+impl Error for ConcurrencyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Cell { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display_and_source() {
+        let err = ConcurrencyError::Cell {
+            row: 1,
+            col: 2,
+            source: Box::new(ConcurrencyError::Overflow { index: 3 }),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Matrix multiply error at cell (1, 2): Dot product overflow at index 3"
+        );
+        assert_eq!(
+            err.source().map(|e| e.to_string()),
+            Some("Dot product overflow at index 3".to_string())
+        );
+
+        // 可以转换成 anyhow::Error, 再 downcast 回来
+        let any: anyhow::Error = ConcurrencyError::ChannelClosed.into();
+        assert_eq!(
+            any.downcast_ref::<ConcurrencyError>(),
+            Some(&ConcurrencyError::ChannelClosed)
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+
+use crate::Matrix;
+
+// 行或列超过这个数量时, 只显示前后各一半, 中间用 ... 代替
+const DEFAULT_MAX_ROWS: usize = 10;
+const DEFAULT_MAX_COLS: usize = 10;
+
+// 矩阵的显示风格, 以 [[1, 2], [3, 4]] 为例:
+// - Pretty: 多行, 每列右对齐
+//   [1 2]
+//   [3 4]
+// - Compact: 一行, {1 2, 3 4}
+// - Markdown: 表格, 表头为列号
+// - Latex: \begin{bmatrix} 1 & 2 \\ 3 & 4 \end{bmatrix}
+// - Numpy: np.array([[1, 2], [3, 4]])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Pretty,
+    Compact,
+    Markdown,
+    Latex,
+    Numpy,
+}
+
+// 可配置的矩阵显示, 通过 Matrix::display() 获取
+// 格式参数中的精度会传给每个元素, 比如 format!("{:.3}", m.display()) 中浮点数保留 3 位小数
+pub struct MatrixDisplay<'a, T> {
+    matrix: &'a Matrix<T>,
+    style: Style,
+    max_rows: usize,
+    max_cols: usize,
+}
+
+impl<T> Matrix<T> {
+    pub fn display(&self) -> MatrixDisplay<'_, T> {
+        MatrixDisplay {
+            matrix: self,
+            style: Style::Pretty,
+            max_rows: DEFAULT_MAX_ROWS,
+            max_cols: DEFAULT_MAX_COLS,
+        }
+    }
+}
+
+impl<T> MatrixDisplay<'_, T> {
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    //最多显示多少行, usize::MAX 表示不省略
+    pub fn max_rows(mut self, n: usize) -> Self {
+        self.max_rows = n;
+        self
+    }
+
+    //最多显示多少列, usize::MAX 表示不省略
+    pub fn max_cols(mut self, n: usize) -> Self {
+        self.max_cols = n;
+        self
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MatrixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let grid = self.grid(|v| match precision {
+            Some(p) => format!("{:.*}", p, v),
+            None => v.to_string(),
+        });
+        grid.render(self.style, f)
+    }
+}
+
+// Debug 使用元素的 Debug 格式, 所以 T 只需要实现 Debug
+impl<T: fmt::Debug> fmt::Debug for MatrixDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let grid = self.grid(|v| match precision {
+            Some(p) => format!("{:.*?}", p, v),
+            None => format!("{:?}", v),
+        });
+        grid.render(self.style, f)
+    }
+}
+
+impl<T> MatrixDisplay<'_, T> {
+    //先把要显示的元素格式化成字符串, 被省略的行列为 None
+    fn grid(&self, cell: impl Fn(&T) -> String) -> Grid {
+        let m = self.matrix;
+        let rows = elide(m.row(), self.max_rows);
+        let cols = elide(m.col(), self.max_cols);
+        let cells = rows
+            .iter()
+            .map(|i| {
+                cols.iter()
+                    .map(|j| match (i, j) {
+                        (Some(i), Some(j)) => Some(cell(&m.data()[i * m.col() + j])),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        Grid { rows, cols, cells }
+    }
+}
+
+// 需要显示的下标, n 超过 max 时中间插入一个 None 代表省略
+fn elide(n: usize, max: usize) -> Vec<Option<usize>> {
+    if n <= max {
+        return (0..n).map(Some).collect();
+    }
+    let head = max.div_ceil(2);
+    let tail = max / 2;
+    (0..head)
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain((n - tail..n).map(Some))
+        .collect()
+}
+
+struct Grid {
+    rows: Vec<Option<usize>>,
+    cols: Vec<Option<usize>>,
+    cells: Vec<Vec<Option<String>>>,
+}
+
+impl Grid {
+    //每一列的最大宽度, 用于对齐
+    fn widths(&self, ellipsis: &str) -> Vec<usize> {
+        (0..self.cols.len())
+            .map(|j| {
+                self.cells
+                    .iter()
+                    .map(|r| r[j].as_deref().unwrap_or(ellipsis).chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    //每一行的单元格, 省略的位置用 ellipsis 代替, 传入 widths 时按列宽右对齐
+    fn row_cells<'a>(
+        &'a self,
+        i: usize,
+        ellipsis: &'a str,
+        widths: Option<&'a [usize]>,
+    ) -> Vec<String> {
+        self.cells[i]
+            .iter()
+            .enumerate()
+            .map(|(j, c)| {
+                let s = c.as_deref().unwrap_or(ellipsis);
+                match widths {
+                    Some(w) => format!("{:>1$}", s, w[j]),
+                    None => s.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    fn render(&self, style: Style, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match style {
+            Style::Pretty => self.pretty(f),
+            Style::Compact => self.compact(f),
+            Style::Markdown => self.markdown(f),
+            Style::Latex => self.latex(f),
+            Style::Numpy => self.numpy(f),
+        }
+    }
+
+    fn pretty(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.rows.is_empty() {
+            return write!(f, "[]");
+        }
+        let widths = self.widths("...");
+        for i in 0..self.rows.len() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[{}]", self.row_cells(i, "...", Some(&widths)).join(" "))?;
+        }
+        Ok(())
+    }
+
+    // 和之前的 Display 格式一致: {1 2 3, 4 5 6}
+    fn compact(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = (0..self.rows.len())
+            .map(|i| self.row_cells(i, "...", None).join(" "))
+            .collect::<Vec<_>>();
+        write!(f, "{{{}}}", rows.join(", "))
+    }
+
+    fn markdown(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = self
+            .cols
+            .iter()
+            .map(|j| j.map_or("...".to_string(), |j| j.to_string()))
+            .collect::<Vec<_>>();
+        let widths = self
+            .widths("...")
+            .iter()
+            .zip(&header)
+            .map(|(w, h)| (*w).max(h.len()).max(3))
+            .collect::<Vec<_>>();
+        let header = header
+            .iter()
+            .zip(&widths)
+            .map(|(h, w)| format!("{:>1$}", h, w))
+            .collect::<Vec<_>>();
+        write!(f, "| {} |", header.join(" | "))?;
+        let sep = widths
+            .iter()
+            .map(|w| format!("{}:", "-".repeat(w - 1)))
+            .collect::<Vec<_>>();
+        write!(f, "\n| {} |", sep.join(" | "))?;
+        for i in 0..self.rows.len() {
+            write!(
+                f,
+                "\n| {} |",
+                self.row_cells(i, "...", Some(&widths)).join(" | ")
+            )?;
+        }
+        Ok(())
+    }
+
+    // 省略的行用 \vdots, 省略的列用 \cdots, 行列都省略的位置用 \ddots
+    fn latex(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\\begin{{bmatrix}}")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            let cells = self.cells[i]
+                .iter()
+                .zip(&self.cols)
+                .map(|(c, col)| match (c, row, col) {
+                    (Some(s), _, _) => s.as_str(),
+                    (None, None, None) => "\\ddots",
+                    (None, None, _) => "\\vdots",
+                    (None, _, _) => "\\cdots",
+                })
+                .collect::<Vec<_>>();
+            write!(f, "{}", cells.join(" & "))?;
+            if i != self.rows.len() - 1 {
+                write!(f, " \\\\")?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "\\end{{bmatrix}}")
+    }
+
+    fn numpy(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = (0..self.rows.len())
+            .map(|i| format!("[{}]", self.row_cells(i, "...", None).join(", ")))
+            .collect::<Vec<_>>();
+        write!(f, "np.array([{}])", rows.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_alignment_and_precision() {
+        let a = Matrix::new([1, 200, 30, 4], 2, 2);
+        assert_eq!(a.to_string(), "[ 1 200]\n[30   4]");
+        let b = Matrix::new([1.0, 2.5, -3.25, 4.0], 2, 2);
+        assert_eq!(format!("{:.2}", b), "[ 1.00 2.50]\n[-3.25 4.00]");
+    }
+
+    #[test]
+    fn test_elision() {
+        let a = Matrix::new((0..100).collect::<Vec<_>>(), 10, 10);
+        let s = a.display().max_rows(4).max_cols(3).style(Style::Compact);
+        assert_eq!(
+            s.to_string(),
+            "{0 1 ... 9, 10 11 ... 19, ... ... ... ..., 80 81 ... 89, 90 91 ... 99}"
+        );
+        let s = a.display().max_rows(2).max_cols(2);
+        assert_eq!(s.to_string(), "[  0 ...   9]\n[... ... ...]\n[ 90 ...  99]");
+    }
+
+    #[test]
+    fn test_alternate_renderers() {
+        let a = Matrix::new([1, 2, 3, 40], 2, 2);
+        assert_eq!(
+            a.display().style(Style::Markdown).to_string(),
+            "|   0 |   1 |\n| --: | --: |\n|   1 |   2 |\n|   3 |  40 |"
+        );
+        assert_eq!(
+            a.display().style(Style::Latex).to_string(),
+            "\\begin{bmatrix}\n1 & 2 \\\\\n3 & 40\n\\end{bmatrix}"
+        );
+        assert_eq!(
+            a.display().style(Style::Numpy).to_string(),
+            "np.array([[1, 2], [3, 40]])"
+        );
+        let big = Matrix::new((0..16).collect::<Vec<_>>(), 4, 4);
+        let latex = big.display().max_rows(2).max_cols(2).style(Style::Latex);
+        assert_eq!(
+            latex.to_string(),
+            "\\begin{bmatrix}\n0 & \\cdots & 3 \\\\\n\\vdots & \\ddots & \\vdots \\\\\n12 & \\cdots & 15\n\\end{bmatrix}"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{multiply, pool, ConcurrencyError, Matrix, Result, Scalar, Vector, Zero};
+
+// 基于邻接矩阵的图算法, adj[i][j] 不为 0 表示有一条 i -> j 的边, 带权图中它就是边的权重
+// 矩阵乘法都使用并行的 multiply, 其他逐行的计算使用 pool 的 worker
+
+// 传递闭包, reachable(i, j) 表示存在一条长度至少为 1 的 i -> j 路径
+pub struct Closure {
+    reach: Matrix<u64>,
+}
+
+impl Closure {
+    pub fn reachable(&self, from: usize, to: usize) -> bool {
+        self.reach.data()[from * self.reach.col() + to] != 0
+    }
+
+    //可达矩阵, 1 表示可达
+    pub fn matrix(&self) -> &Matrix<u64> {
+        &self.reach
+    }
+}
+
+// 多源最短路径的结果, 可以查询距离和还原路径
+pub struct ShortestPaths<T> {
+    n: usize,
+    dist: Vec<Option<T>>,
+    //next[i][j] 为 i 到 j 的最短路径上 i 的下一个节点
+    next: Vec<Option<usize>>,
+}
+
+impl<T: Copy> ShortestPaths<T> {
+    //不可达时返回 None
+    pub fn distance(&self, from: usize, to: usize) -> Option<T> {
+        self.dist[from * self.n + to]
+    }
+
+    //最短路径经过的节点, 包含起点和终点, 不可达时返回 None
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        self.next[from * self.n + to]?;
+        let mut path = vec![from];
+        let mut cur = from;
+        while cur != to {
+            cur = self.next[cur * self.n + to]?;
+            path.push(cur);
+        }
+        Some(path)
+    }
+}
+
+// 无向图中的三角形数量, per_node[i] 为包含节点 i 的三角形个数
+#[derive(Debug, PartialEq, Eq)]
+pub struct Triangles {
+    pub total: u64,
+    pub per_node: Vec<u64>,
+}
+
+// PageRank 的结果, converged 为 false 表示达到 max_iter 时还没有收敛
+pub struct PageRank {
+    pub scores: Vector<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn check_square<T>(adj: &Matrix<T>) -> Result<usize> {
+    if adj.row() != adj.col() {
+        return Err(ConcurrencyError::NotSquare {
+            op: "Graph",
+            shape: (adj.row(), adj.col()),
+        });
+    }
+    Ok(adj.row())
+}
+
+// 0/1 矩阵, 不为 0 的位置为 1
+fn to_binary<T: Zero + Copy + Sync>(adj: &Matrix<T>) -> Result<Matrix<u64>> {
+    adj.par_map(|v| !v.is_zero() as u64)
+}
+
+// R 表示长度在 [1, 2^k] 之间的路径, R = R + R * R 重复平方, 最多 log(n) + 1 次就不再变化
+pub fn transitive_closure<T: Zero + Copy + Sync>(adj: &Matrix<T>) -> Result<Closure> {
+    check_square(adj)?;
+    let mut reach = to_binary(adj)?;
+    loop {
+        let squared = multiply(&reach, &reach)?;
+        let next = reach.par_zip_with(&squared, |a, b| (a != 0 || b != 0) as u64)?;
+        if next.data() == reach.data() {
+            return Ok(Closure { reach });
+        }
+        reach = next;
+    }
+}
+
+// Floyd-Warshall 多源最短路径, 允许负权边, 有负环时返回错误
+// 外层的 k 必须按顺序执行, 每一轮中各行的更新互不依赖, 按行切分后并行计算
+pub fn floyd_warshall<T>(adj: &Matrix<T>) -> Result<ShortestPaths<T>>
+where
+    T: Scalar + PartialOrd,
+{
+    let n = check_square(adj)?;
+    let mut dist = vec![None; n * n];
+    let mut next = vec![None; n * n];
+    for i in 0..n {
+        dist[i * n + i] = Some(T::zero());
+        next[i * n + i] = Some(i);
+        for j in 0..n {
+            let w = adj.data()[i * n + j];
+            if w.is_zero() || (i == j && w > T::zero()) {
+                continue;
+            }
+            dist[i * n + j] = Some(w);
+            next[i * n + j] = Some(j);
+        }
+    }
+
+    for k in 0..n {
+        let (d, nx) = (&dist, &next);
+        let chunks = pool::run(pool::split(n, n), |rows| {
+            let mut dist_rows = d[rows.start * n..rows.end * n].to_vec();
+            let mut next_rows = nx[rows.start * n..rows.end * n].to_vec();
+            for i in rows.clone() {
+                let Some(ik) = d[i * n + k] else {
+                    continue;
+                };
+                let base = (i - rows.start) * n;
+                for j in 0..n {
+                    let Some(kj) = d[k * n + j] else {
+                        continue;
+                    };
+                    let candidate = ik + kj;
+                    if dist_rows[base + j].is_none_or(|v| candidate < v) {
+                        dist_rows[base + j] = Some(candidate);
+                        next_rows[base + j] = nx[i * n + k];
+                    }
+                }
+            }
+            (dist_rows, next_rows)
+        })?;
+        dist.clear();
+        next.clear();
+        for (d, nx) in chunks {
+            dist.extend(d);
+            next.extend(nx);
+        }
+    }
+
+    if let Some(i) = (0..n).find(|&i| dist[i * n + i].is_some_and(|v| v < T::zero())) {
+        return Err(ConcurrencyError::NegativeCycle { node: i });
+    }
+    Ok(ShortestPaths { n, dist, next })
+}
+
+// 无向图的三角形计数, (A * A)[i][j] 是 i 到 j 长度为 2 的路径数, 再和 A 按元素相乘
+// 就只保留 i, j 之间也有边的情况, 每个三角形在节点 i 这一行中被数了 2 次
+// 自环不算三角形, 而且会经过 A[i][i] * A[i][j] 多数出长度为 2 的路径, 所以先把对角线清零
+pub fn count_triangles<T: Zero + Copy + Sync>(adj: &Matrix<T>) -> Result<Triangles> {
+    let n = check_square(adj)?;
+    let mut data = to_binary(adj)?.data().to_vec();
+    for i in 0..n {
+        data[i * n + i] = 0;
+    }
+    let a = Matrix::new(data, n, n);
+    let a2 = multiply(&a, &a)?;
+    let hadamard = a2.par_zip_with(&a, |x, y| x * y)?;
+    let per_node = hadamard
+        .sum_rows()?
+        .iter()
+        .map(|v| v / 2)
+        .collect::<Vec<_>>();
+    Ok(Triangles {
+        total: per_node.iter().sum::<u64>() / 3,
+        per_node,
+    })
+}
+
+// PageRank 幂迭代: r = damping * M * r + (1 - damping) / n
+// M 是按列归一化的转移矩阵, 出度为 0 的节点把分数平均分给所有节点
+// 两次迭代之间的 L1 距离小于 tol 时认为收敛
+pub fn pagerank<T: Zero + Copy + Sync>(
+    adj: &Matrix<T>,
+    damping: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<PageRank> {
+    let n = check_square(adj)?;
+    if n == 0 {
+        return Ok(PageRank {
+            scores: Vector::new(vec![]),
+            iterations: 0,
+            converged: true,
+        });
+    }
+    let a = to_binary(adj)?;
+    let out_degree = a.sum_rows()?;
+    //M[j][i] = 1 / out_degree(i), 即 i -> j 的转移概率
+    let mut m = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            m[j * n + i] = if out_degree[i] == 0 {
+                1.0 / n as f64
+            } else {
+                a.data()[i * n + j] as f64 / out_degree[i] as f64
+            };
+        }
+    }
+    let m = Matrix::new(m, n, n);
+
+    let teleport = (1.0 - damping) / n as f64;
+    let mut rank = Matrix::new(vec![1.0 / n as f64; n], n, 1);
+    for iteration in 1..=max_iter {
+        let next = multiply(&m, &rank)?.par_map(|v| damping * v + teleport)?;
+        let delta: f64 = next
+            .data()
+            .iter()
+            .zip(rank.data())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        rank = next;
+        if delta < tol {
+            return Ok(PageRank {
+                scores: Vector::new(rank.data()),
+                iterations: iteration,
+                converged: true,
+            });
+        }
+    }
+    Ok(PageRank {
+        scores: Vector::new(rank.data()),
+        iterations: max_iter,
+        converged: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitive_closure() -> Result<()> {
+        // 0 -> 1 -> 2, 3 -> 3
+        let adj = Matrix::new([0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1], 4, 4);
+        let c = transitive_closure(&adj)?;
+        assert!(c.reachable(0, 2));
+        assert!(!c.reachable(2, 0));
+        assert!(!c.reachable(0, 0));
+        assert!(c.reachable(3, 3));
+        assert!(transitive_closure(&Matrix::new([1, 2], 1, 2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_floyd_warshall() -> Result<()> {
+        // 0 -4-> 1, 0 -1-> 2, 2 -2-> 1, 1 -1-> 3
+        let adj = Matrix::new([0, 4, 1, 0, 0, 0, 0, 1, 0, 2, 0, 0, 0, 0, 0, 0], 4, 4);
+        let sp = floyd_warshall(&adj)?;
+        assert_eq!(sp.distance(0, 3), Some(4));
+        assert_eq!(sp.path(0, 3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(sp.distance(3, 0), None);
+        assert_eq!(sp.path(3, 0), None);
+        assert_eq!(sp.path(2, 2), Some(vec![2]));
+
+        let negative = Matrix::new([0, 1, -2, 0], 2, 2);
+        assert!(matches!(
+            floyd_warshall(&negative),
+            Err(ConcurrencyError::NegativeCycle { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_triangles() -> Result<()> {
+        // 0-1-2 构成三角形, 2-3 是一条边
+        let adj = Matrix::new([0, 1, 1, 0, 1, 0, 1, 0, 1, 1, 0, 1, 0, 0, 1, 0], 4, 4);
+        let t = count_triangles(&adj)?;
+        assert_eq!(
+            t,
+            Triangles {
+                total: 1,
+                per_node: vec![1, 1, 1, 0]
+            }
+        );
+
+        // 0-1-2 是一条路径, 0 和 2 上有自环, 没有三角形
+        let adj = Matrix::new([1, 1, 0, 1, 0, 1, 0, 1, 1], 3, 3);
+        assert_eq!(
+            count_triangles(&adj)?,
+            Triangles {
+                total: 0,
+                per_node: vec![0, 0, 0]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pagerank() -> Result<()> {
+        // 0 -> 1, 1 -> 2, 2 -> 0, 2 -> 1: 1 的入边最多
+        let adj = Matrix::new([0, 1, 0, 0, 0, 1, 1, 1, 0], 3, 3);
+        let pr = pagerank(&adj, 0.85, 1e-10, 100)?;
+        assert!(pr.converged);
+        assert!((pr.scores.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(pr.scores[1] > pr.scores[0]);
+        assert!(pr.scores[1] > pr.scores[2]);
+        Ok(())
+    }
+}
@@ -1,7 +1,47 @@
+mod accumulator;
+mod broadcast;
+mod complex;
+mod conv;
+mod error;
+mod format;
+mod graph;
 mod matrix;
 mod metrics;
+mod num;
+mod parallel;
+mod pool;
+mod reduce;
+mod regression;
+mod simd;
+mod smatrix;
+mod sort;
+mod sparse;
+mod stats;
+mod structure;
 mod vector;
 
-pub use matrix::{multiply, Matrix};
-pub use metrics::{AmapMetrics, CmapMetrics};
-pub use vector::{dot_product, Vector};
+pub use accumulator::{
+    Accumulator, Checked, Fma, Kahan, Neumaier, Pairwise, Plain, Saturating, Widening, Wrapping,
+};
+pub use broadcast::{broadcast, Broadcast};
+pub use complex::{hdot, Complex};
+pub use conv::Padding;
+pub use error::{ConcurrencyError, Result};
+pub use format::{MatrixDisplay, Style};
+pub use graph::{
+    count_triangles, floyd_warshall, pagerank, transitive_closure, Closure, PageRank,
+    ShortestPaths, Triangles,
+};
+pub use matrix::{multiply, multiply_with, Matrix, MultiplyOptions, Progress, MULTIPLY_METRICS};
+pub use metrics::{AmapMetrics, CmapMetrics, Metrics};
+pub use num::{Field, One, Real, Scalar, Zero};
+pub use regression::LinearRegression;
+pub use simd::Simd;
+pub use smatrix::SMatrix;
+pub use sparse::SparseVector;
+pub use stats::{Histogram, Welford};
+pub use structure::{block, hstack, kron, vstack};
+pub use vector::{
+    dot, dot_as, dot_iter, dot_product, dot_product_with, par_dot_product, AsVectorView, Distance,
+    Vector, VectorView,
+};
@@ -1,46 +1,129 @@
-use anyhow::{anyhow, Result};
 use std::{
     fmt,
-    ops::{Add, AddAssign, Mul},
-    sync::mpsc,
-    thread,
+    ops::{Mul, Neg, Sub},
+    sync::Arc,
+    time::Duration,
 };
 
-use crate::{dot_product, Vector};
+use crate::{
+    dot_product_with,
+    pool::{self, NUM_THREADS},
+    Accumulator, Complex, ConcurrencyError, Metrics, Plain, Result, Scalar, Style, VectorView,
+};
+
+// multiply 写入 metrics 的 key, 使用 AmapMetrics 时需要先用 MULTIPLY_METRICS 注册
+const CELLS_KEY: &str = "multiply.cells";
+const BLOCKS_KEY: &str = "multiply.blocks";
+// 每个 worker 的 sender 中还没处理的消息数
+const QUEUE_KEYS: [&str; NUM_THREADS] = [
+    "multiply.worker.0.queue",
+    "multiply.worker.1.queue",
+    "multiply.worker.2.queue",
+    "multiply.worker.3.queue",
+];
+// 每个 worker 执行 dot_product 的累计耗时(纳秒)
+const BUSY_KEYS: [&str; NUM_THREADS] = [
+    "multiply.worker.0.busy_ns",
+    "multiply.worker.1.busy_ns",
+    "multiply.worker.2.busy_ns",
+    "multiply.worker.3.busy_ns",
+];
+
+// multiply 会用到的全部指标, 可以直接传给 AmapMetrics::new
+pub const MULTIPLY_METRICS: [&str; 2 + 2 * NUM_THREADS] = [
+    CELLS_KEY,
+    BLOCKS_KEY,
+    QUEUE_KEYS[0],
+    QUEUE_KEYS[1],
+    QUEUE_KEYS[2],
+    QUEUE_KEYS[3],
+    BUSY_KEYS[0],
+    BUSY_KEYS[1],
+    BUSY_KEYS[2],
+    BUSY_KEYS[3],
+];
+
+// 进度回调的参数, done 为已完成的单元格数量, total 为结果矩阵的单元格总数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+// multiply 的可选配置, 默认什么都不记录, 使用 Plain 累加, 和 multiply 行为一致
+// metrics 会 clone 到每个 worker 线程中, 所以这里用 Arc 包一层
+// progress 回调只在调用线程中执行(reduce 阶段), 所以不需要 Send
+#[derive(Default)]
+pub struct MultiplyOptions<'a, A = Plain> {
+    metrics: Option<Arc<dyn Metrics>>,
+    progress: Option<Box<dyn FnMut(Progress) + 'a>>,
+    accumulator: A,
+}
+
+impl<'a> MultiplyOptions<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a, A> MultiplyOptions<'a, A> {
+    // 传入 CmapMetrics / AmapMetrics 的 clone 即可, 内部数据是 Arc 共享的
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
 
-//4个线程
-const NUM_THREADS: usize = 4;
+    pub fn progress(mut self, f: impl FnMut(Progress) + 'a) -> Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
 
-//多线程出入参定义
-pub struct MsgInput<T> {
-    idx: usize,
-    row: Vector<T>,
-    col: Vector<T>,
+    // 替换累加策略, 结果矩阵的元素类型跟着变成 B::Output
+    pub fn accumulator<B>(self, accumulator: B) -> MultiplyOptions<'a, B> {
+        MultiplyOptions {
+            metrics: self.metrics,
+            progress: self.progress,
+            accumulator,
+        }
+    }
 }
 
-impl<T> MsgInput<T> {
-    fn new(idx: usize, row: Vector<T>, col: Vector<T>) -> Self {
-        Self { idx, row, col }
+// metrics 只是观测用的, 写入失败不应该影响计算结果, 这里只打印错误
+fn record(metrics: &Option<Arc<dyn Metrics>>, key: &str, delta: i64) {
+    if let Some(m) = metrics {
+        if let Err(e) = m.add(key, delta) {
+            eprintln!("Metrics error: {:?}", e);
+        }
     }
 }
 
-pub struct MsgOutput<T> {
-    idx: usize,
-    //返回累加和
-    value: T,
+// 把 pool 中每个 worker 的运行情况写入 metrics
+struct MetricsObserver<'a>(&'a Option<Arc<dyn Metrics>>);
+
+impl pool::Observer for MetricsObserver<'_> {
+    fn queued(&self, worker: usize) {
+        record(self.0, QUEUE_KEYS[worker], 1);
+    }
+
+    fn started(&self, worker: usize) {
+        record(self.0, QUEUE_KEYS[worker], -1);
+    }
+
+    fn finished(&self, worker: usize, elapsed: Duration) {
+        record(self.0, BUSY_KEYS[worker], elapsed.as_nanos() as i64);
+    }
 }
 
-//组装 输入消息和输出消息到一个数据结构中, reduce的时候可以获取到所有的信息
-// 这个数据结构的返回应该是获取MsgOutput的流,
-pub struct Msg<T> {
-    input: MsgInput<T>,
-    //sender to send the result back
-    sender: oneshot::Sender<MsgOutput<T>>,
+//多线程的任务输入, 结果通过 pool 按 idx 的顺序返回
+//row 和 col 都是借用的视图, 不会为每个单元格复制数据
+pub struct MsgInput<'a, T> {
+    row: VectorView<'a, T>,
+    col: VectorView<'a, T>,
 }
 
-impl<T> Msg<T> {
-    fn new(input: MsgInput<T>, sender: oneshot::Sender<MsgOutput<T>>) -> Self {
-        Self { input, sender }
+impl<'a, T> MsgInput<'a, T> {
+    fn new(row: VectorView<'a, T>, col: VectorView<'a, T>) -> Self {
+        Self { row, col }
     }
 }
 
@@ -52,13 +135,14 @@ impl<T> Msg<T> {
 //如果要自定义Matrix 的 debug内容, 则自行实现 display 和 debug trait, 这里就先注释掉
 // #[derive(Debug)]
 // pub struct Matrix<T: fmt::Debug> {
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Matrix<T> {
     data: Vec<T>,
     row: usize,
     col: usize,
 }
 
-impl<T: fmt::Debug> Matrix<T> {
+impl<T> Matrix<T> {
     //这里使用 impl Into<Vec<T>> , 表示的是 只要能转化成 Vec<T> 就可以作为参数传入
     pub fn new(data: impl Into<Vec<T>>, row: usize, col: usize) -> Self {
         Self {
@@ -67,12 +151,162 @@ impl<T: fmt::Debug> Matrix<T> {
             col,
         }
     }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    //按行平铺的数据, 第 i 行第 j 列为 data[i * col + j]
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+}
+
+// 二维数组直接转成矩阵, 形状由类型决定: Matrix::from([[1, 2], [3, 4]])
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        Self::new(rows.into_iter().flatten().collect::<Vec<_>>(), R, C)
+    }
+}
+
+// 按行 collect 成矩阵, 每一行的长度必须一致, 否则 panic
+// FromIterator 没有办法返回错误, 需要检查的话先 collect 成 Vec<Vec<T>> 再 block / vstack
+impl<T> FromIterator<Vec<T>> for Matrix<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+        let mut m = Self::new(vec![], 0, 0);
+        m.extend(iter);
+        m
+    }
+}
+
+// 在矩阵下面追加行, 行的长度必须等于 col, 否则 panic, 0x0 的矩阵的 col 由第一行决定
+impl<T> Extend<Vec<T>> for Matrix<T> {
+    fn extend<I: IntoIterator<Item = Vec<T>>>(&mut self, iter: I) {
+        for row in iter {
+            if self.row == 0 && self.col == 0 {
+                self.col = row.len();
+            }
+            assert_eq!(
+                row.len(),
+                self.col,
+                "Matrix extend error: row {} has {} columns, expected {}",
+                self.row,
+                row.len(),
+                self.col
+            );
+            self.data.extend(row);
+            self.row += 1;
+        }
+    }
+}
+
+// 按行优先的顺序遍历所有元素
+impl<T> IntoIterator for Matrix<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
 }
 
-impl<T> Mul for Matrix<T>
+impl<'a, T> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+// 浮点数矩阵不能直接用 == 比较, 这里提供一个按元素误差比较的方法, 形状不同直接返回 false
+// 先比较大小再用大的减小的来代替 abs, 减法不会下溢, 这样无符号整数矩阵也可以用
+impl<T> Matrix<T>
 where
-    T: fmt::Debug + Add<Output = T> + Copy + AddAssign + Mul<Output = T> + Default + Send + 'static,
+    T: Copy + Sub<Output = T> + PartialOrd,
 {
+    pub fn approx_eq(&self, other: &Self, epsilon: T) -> bool {
+        self.row == other.row
+            && self.col == other.col
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(&a, &b)| if a >= b { a - b } else { b - a } <= epsilon)
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn zeros(row: usize, col: usize) -> Self {
+        Self::new(vec![T::zero(); row * col], row, col)
+    }
+
+    //n*n 的单位矩阵, 对角线为 1, 其他为 0
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m.data[i * n + i] = T::one();
+        }
+        m
+    }
+
+    //矩阵的 n 次幂, 使用快速幂, 只需要 log(n) 次 multiply
+    //只有方阵才能求幂, pow(0) 返回单位矩阵
+    pub fn pow(&self, mut n: u32) -> Result<Self> {
+        if self.row != self.col {
+            return Err(ConcurrencyError::NotSquare {
+                op: "Matrix pow",
+                shape: (self.row, self.col),
+            });
+        }
+        let mut result = Self::identity(self.row);
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = multiply(&result, &base)?;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = multiply(&base, &base)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Copy> Matrix<T> {
+    //转置, row 和 col 互换
+    pub fn transpose(&self) -> Self {
+        let mut data = Vec::with_capacity(self.data.len());
+        for j in 0..self.col {
+            for i in 0..self.row {
+                data.push(self.data[i * self.col + j]);
+            }
+        }
+        Self {
+            data,
+            row: self.col,
+            col: self.row,
+        }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Matrix<Complex<T>> {
+    //共轭转置 (Hermitian transpose), 转置后每个元素取共轭
+    pub fn conj_transpose(&self) -> Self {
+        let mut m = self.transpose();
+        for v in m.data.iter_mut() {
+            *v = v.conj();
+        }
+        m
+    }
+}
+
+impl<T: Scalar> Mul for Matrix<T> {
     type Output = Self;
 
     fn mul(self, rhs: Matrix<T>) -> Self::Output {
@@ -83,52 +317,53 @@ where
 pub fn multiply<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>>
 //对泛型T进行约束,
 //简单的约束可以直接在 <T: ...> 中进行, 复杂的约束就再函数签名后面 加上 where
+//这里的一长串约束已经收敛到 Scalar 中
 where
-    T: fmt::Debug + Add<Output = T> + Copy + AddAssign + Mul<Output = T> + Default + Send + 'static,
+    T: Scalar,
+{
+    multiply_with(a, b, MultiplyOptions::new())
+}
+
+// 和 multiply 一样, 额外把进度和每个 worker 的运行情况写入 options 中的 metrics / progress
+// 每个单元格的点乘使用 options 中的累加策略, 溢出错误会带上单元格的位置返回
+pub fn multiply_with<T, A>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    mut options: MultiplyOptions<A>,
+) -> Result<Matrix<A::Output>>
+where
+    T: Copy + Send + Sync,
+    A: Accumulator<T> + Sync,
+    A::Output: Send,
 {
     //这个边界值不懂
     if a.col != b.row {
-        return Err(anyhow!(
-            "Matrix dimensions do not match error: a.col != b.row"
-        ));
+        return Err(ConcurrencyError::ShapeMismatch {
+            op: "Matrix multiply",
+            left: (a.row, a.col),
+            right: (b.row, b.col),
+        });
     }
 
-    //每个线程1个channel
-    let senders = (0..NUM_THREADS)
-        .map(|_| {
-            let (tx, rx) = mpsc::channel::<Msg<T>>();
-            thread::spawn(move || {
-                for msg in rx {
-                    //这里泛型T没有实现send trait, 无法安全的在线程中传递, 上面的where中添加Send+'static trait
-                    let value = dot_product(msg.input.row, msg.input.col)?;
-                    if let Err(e) = msg.sender.send(MsgOutput {
-                        idx: msg.input.idx,
-                        value,
-                    }) {
-                        eprintln!("Send error: {:?}", e);
-                    }
-                }
-                //这里无法直接返回 OK(()))
-                Ok::<_, anyhow::Error>(())
-            });
-            //这里返回tx, rx被必包到上面的线程处理中
-            tx
-        })
-        //将sender收集起来
-        .collect::<Vec<_>>();
+    // 先把所有 key 都写一遍 0, AmapMetrics 没有注册的 key 在这里就直接报错, 而不是在 worker 里面
+    if let Some(m) = &options.metrics {
+        for key in MULTIPLY_METRICS {
+            m.add(key, 0)?;
+        }
+    }
 
     //这里确定结果的容量
-    //这里我们要使用 mut 获取 data的所有权, 因为后面需要修改 data的内容, 有修改操作
-    //这样写有个问题就是 生成的 Vec 元素 = 空, T泛型没有默认值处理
-    // let mut data = Vec::with_capacity(a.row*b.col);
     let length = a.row * b.col;
-    let mut data = vec![T::default(); length];
-
-    //多线程返回多个rx，收集rx进行reduce
-    let mut receives = Vec::with_capacity(length);
+    let mut inputs = Vec::with_capacity(length);
 
     //矩阵乘法算法
     //先遍历a的每一行, 再遍历b的每一列, 然后计算对应位置的乘积, 然后加到结果矩阵的对应位置上
+    //需要连续切片的累加策略(比如 Simd), 先把 b 整体转置一次, b 的第 j 列就是 bt 的第 j 行
+    let bt = options
+        .accumulator
+        .prefers_contiguous()
+        .then(|| b.transpose());
+
     for i in 0..a.row {
         for j in 0..b.col {
             // 这一步用 dot_product 替代
@@ -141,34 +376,50 @@ where
             //取a的行值, 因为这里是 a矩阵的切片类型
             //切片类型本身不是一个 Vec<T>，而是一个指向 Vec<T> 内部元素的引用。
             //所以这里需要进行取引用符号
-            let row = Vector::new(&a.data[a.col * i..a.col * (i + 1)]);
-            //copied() 表示将引用里面的值转出值, 而不是引用本身
-            let col_data = b.data[j..]
-                .iter()
-                .step_by(b.col)
-                .copied()
-                .collect::<Vec<T>>();
-            let col = Vector::new(col_data);
+            let row = VectorView::new(&a.data[a.col * i..a.col * (i + 1)]);
+            //否则 b 的第 j 列在内存中不连续, 用 stride 为 b.col 的视图直接借用, 累加策略按 stride 读取, 不复制也不转置
+            let col = match &bt {
+                Some(bt) => VectorView::new(&bt.data[bt.col * j..bt.col * (j + 1)]),
+                None => VectorView::strided(&b.data, j, b.col, b.row)?,
+            };
             //这里改成多线程处理
             // data[i * b.col + j] += dot_product(row, col)?;
-
-            // 每个都生成 msgInput 和  新增一个channel ，将 tx放入到msg中， rx保留做最后的reduce
-            let idx = i * b.col + j;
-            let msg_input = MsgInput::new(idx, row, col);
-            let (tx, rx) = oneshot::channel();
-            let msg = Msg::new(msg_input, tx);
-            //sender返回的不是Result, 所以不能用？号
-            if let Err(e) = senders[idx % NUM_THREADS].send(msg) {
-                eprint!("Send error: {:?}", e)
-            }
-            receives.push(rx);
+            inputs.push(MsgInput::new(row, col));
         }
     }
 
-    //reduce rx结果
-    for rx in receives {
-        let recv = rx.recv()?;
-        data[recv.idx] = recv.value
+    //每个单元格是一个任务, 交给 pool 的 worker 并行计算, 结果按 idx 的顺序返回
+    //pool 使用 scoped thread, 累加策略可以直接借用, 不需要 clone 到每个线程
+    let accumulator = &options.accumulator;
+    let observer = MetricsObserver(&options.metrics);
+    let progress = &mut options.progress;
+    let outputs = pool::run_observed(
+        inputs,
+        |input| dot_product_with(input.row, input.col, accumulator),
+        &observer,
+        //结果是按 idx 顺序返回的, 收到一行的最后一个单元格时, 这一行(block)就完成了
+        |idx, _| {
+            record(&options.metrics, CELLS_KEY, 1);
+            if (idx + 1) % b.col == 0 {
+                record(&options.metrics, BLOCKS_KEY, 1);
+            }
+            if let Some(f) = progress.as_mut() {
+                f(Progress {
+                    done: idx + 1,
+                    total: length,
+                });
+            }
+        },
+    )?;
+
+    //reduce 结果, 第一个出错的单元格带上位置返回
+    let mut data = Vec::with_capacity(length);
+    for (idx, value) in outputs.into_iter().enumerate() {
+        data.push(value.map_err(|e| ConcurrencyError::Cell {
+            row: idx / b.col,
+            col: idx % b.col,
+            source: Box::new(e),
+        })?);
     }
 
     Ok(Matrix {
@@ -179,38 +430,31 @@ where
 }
 
 //md, 这里居然不会提示我实现 fmt 方法, 只是报了一个错...垃圾
+//默认多行显示, 每列右对齐, 大矩阵中间的行列用 ... 省略, 其他风格见 Matrix::display()
 impl<T> fmt::Display for Matrix<T>
 where
     T: fmt::Display,
 {
-    // generate code: display a 2x3 as {1 2 3, 4 5 6}, 3x2 as {1 2, 3 4, 5 6}
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        //矩阵显示的算法
-        write!(f, "{{")?;
-        for i in 0..self.row {
-            for j in 0..self.col {
-                write!(f, "{}", self.data[i * self.col + j])?;
-                if j != self.col - 1 {
-                    write!(f, " ")?;
-                }
-            }
-            if i != self.row - 1 {
-                write!(f, ", ")?;
-            }
-        }
-        write!(f, "}}")?;
-        Ok(())
+        fmt::Display::fmt(&self.display(), f)
     }
 }
 
-//实现 display后, 在实现debug trait, debug trait中的方法可以复用display的结果
+//debug 使用单行的格式 Matrix(row=2, col=2, {1 2, 3 4}), 元素使用 T 的 Debug 格式, 所以不再要求 T: Display
 impl<T> fmt::Debug for Matrix<T>
 where
-    //debug 复用 t的display 格式, 这里T显示指定需要实现 Display trait
-    T: fmt::Display,
+    T: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Matrix(row={}, col={}, {})", self.row, self.col, self)
+        write!(f, "Matrix(row={}, col={}, ", self.row, self.col)?;
+        //Debug 不省略任何元素, 大矩阵 assert_eq! 失败时也能看到不同的单元格, 省略只用在 Display 中
+        let all = self
+            .display()
+            .style(Style::Compact)
+            .max_rows(usize::MAX)
+            .max_cols(usize::MAX);
+        fmt::Debug::fmt(&all, f)?;
+        write!(f, ")")
     }
 }
 
@@ -229,10 +473,19 @@ mod tests {
         //进行这几个比较
         assert_eq!(c.row, 2);
         assert_eq!(c.col, 2);
-        assert_eq!(c.data, [22, 28, 49, 64]);
+        assert_eq!(c, Matrix::from([[22, 28], [49, 64]]));
         //这个是总的比较
         assert_eq!(format!("{:?}", c), "Matrix(row=2, col=2, {22 28, 49 64})");
+        //大矩阵的 Debug 也显示全部元素
+        let big = Matrix::new((0..400).collect::<Vec<_>>(), 20, 20);
+        let debug = format!("{:?}", big);
+        assert!(!debug.contains("..."));
+        assert!(debug.contains(" 210 ") && debug.ends_with(" 399})"));
 
+        //内维为 0 时 b 的列是空视图, 结果全为 0
+        let a = Matrix::<i32>::new([], 2, 0);
+        let b = Matrix::<i32>::new([], 0, 3);
+        assert_eq!(multiply(&a, &b)?, Matrix::zeros(2, 3));
         Ok(())
     }
 
@@ -242,8 +495,8 @@ mod tests {
         let a = Matrix::new([1, 2, 3, 4], 2, 2);
         let b = Matrix::new([1, 2, 3, 4], 2, 2);
         let c = a * b;
-        assert_eq!(c.data, vec![7, 10, 15, 22]);
-        assert_eq!(format!("{}", c), "{7 10, 15 22}");
+        assert_eq!(c, Matrix::from([[7, 10], [15, 22]]));
+        assert_eq!(format!("{}", c), "[ 7 10]\n[15 22]");
         Ok(())
     }
 
@@ -255,6 +508,193 @@ mod tests {
         let _matrix = a * b;
     }
 
+    #[test]
+    fn test_multiply_with_metrics_and_progress() -> Result<()> {
+        let metrics = crate::CmapMetrics::new();
+        let mut progress = Vec::new();
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2);
+        let options = MultiplyOptions::new()
+            .metrics(metrics.clone())
+            .progress(|p| progress.push(p));
+        let c = multiply_with(&a, &b, options)?;
+        assert_eq!(c, Matrix::from([[22, 28], [49, 64]]));
+        assert_eq!(metrics.get(CELLS_KEY), Some(4));
+        assert_eq!(metrics.get(BLOCKS_KEY), Some(2));
+        for key in QUEUE_KEYS {
+            assert_eq!(metrics.get(key), Some(0));
+        }
+        assert_eq!(progress.len(), 4);
+        assert_eq!(progress[3], Progress { done: 4, total: 4 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiply_with_unregistered_amap_metrics() {
+        let metrics = crate::AmapMetrics::new(&[CELLS_KEY]);
+        let a = Matrix::new([1, 2, 3, 4], 2, 2);
+        let options = MultiplyOptions::new().metrics(metrics);
+        assert_eq!(
+            multiply_with(&a, &a, options).unwrap_err(),
+            ConcurrencyError::UnknownMetric(BLOCKS_KEY.to_string())
+        );
+    }
+
+    #[test]
+    fn test_multiply_with_accumulator() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, i32::MAX], 2, 2);
+        let b = Matrix::new([1, 0, 0, 2], 2, 2);
+
+        let options = MultiplyOptions::new().accumulator(crate::Checked);
+        let err = multiply_with(&a, &b, options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Matrix multiply error at cell (1, 1): Dot product overflow at index 1"
+        );
+        assert!(matches!(
+            err,
+            ConcurrencyError::Cell { row: 1, col: 1, source }
+                if *source == ConcurrencyError::Overflow { index: 1 }
+        ));
+
+        let options = MultiplyOptions::new().accumulator(crate::Widening);
+        let c = multiply_with(&a, &b, options)?;
+        assert_eq!(c, Matrix::from([[1, 4], [3, i32::MAX as i64 * 2]]));
+        Ok(())
+    }
+
+    // 包装 Simd, 记录 multiply 传入的列中有多少是带 stride 的视图
+    struct Probe {
+        contiguous: bool,
+        strided: std::sync::atomic::AtomicUsize,
+    }
+
+    impl crate::Accumulator<f32> for Probe {
+        type Output = f32;
+
+        fn dot(&self, a: &[f32], b: &[f32]) -> Result<f32> {
+            crate::Simd.dot(a, b)
+        }
+
+        fn dot_view(&self, a: VectorView<'_, f32>, b: VectorView<'_, f32>) -> Result<f32> {
+            if a.as_slice().is_none() || b.as_slice().is_none() {
+                self.strided
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+            crate::Simd.dot_view(a, b)
+        }
+
+        fn prefers_contiguous(&self) -> bool {
+            self.contiguous
+        }
+    }
+
+    #[test]
+    fn test_multiply_simd_uses_contiguous_columns() -> Result<()> {
+        use crate::Accumulator;
+
+        let n = 16;
+        let a = Matrix::new((0..n * n).map(|v| v as f32).collect::<Vec<_>>(), n, n);
+        let b = Matrix::new((0..n * n).map(|v| (v % 7) as f32).collect::<Vec<_>>(), n, n);
+        let expected = multiply(&a, &b)?;
+
+        //Simd 需要连续的切片, multiply 转置 b 之后每个单元格都走向量化的路径
+        assert!(Accumulator::<f32>::prefers_contiguous(&crate::Simd));
+        let probe = Probe {
+            contiguous: Accumulator::<f32>::prefers_contiguous(&crate::Simd),
+            strided: Default::default(),
+        };
+        let c = multiply_with(&a, &b, MultiplyOptions::new().accumulator(&probe))?;
+        assert_eq!(c, expected);
+        assert_eq!(probe.strided.into_inner(), 0);
+
+        //不需要连续切片的策略直接借用 b 的列, 不转置
+        let probe = Probe {
+            contiguous: false,
+            strided: Default::default(),
+        };
+        let c = multiply_with(&a, &b, MultiplyOptions::new().accumulator(&probe))?;
+        assert_eq!(c, expected);
+        assert_eq!(probe.strided.into_inner(), n * n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiply_with_float_accumulator() -> Result<()> {
+        let a = Matrix::new([0.1, 0.2, 0.3, 0.4], 2, 2);
+        let b = Matrix::new([1.0, 0.0, 0.0, 1.0], 2, 2);
+        let options = MultiplyOptions::new().accumulator(crate::Kahan);
+        let c = multiply_with(&a, &b, options)?;
+        assert!(c.approx_eq(&a, 1e-12));
+        assert!(!c.approx_eq(&b, 1e-12));
+        assert!(!c.approx_eq(&Matrix::new([0.1, 0.2, 0.3, 0.4], 1, 4), 1e-12));
+
+        let a = Matrix::new([1u32, 2], 1, 2);
+        assert!(a.approx_eq(&Matrix::new([2u32, 2], 1, 2), 1));
+        assert!(!a.approx_eq(&Matrix::new([3u32, 0], 1, 2), 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_pow() -> Result<()> {
+        //斐波那契数列的矩阵形式
+        let a = Matrix::new([1u64, 1, 1, 0], 2, 2);
+        assert_eq!(a.pow(10)?, Matrix::from([[89, 55], [55, 34]]));
+        assert_eq!(a.pow(0)?, Matrix::identity(2));
+        assert!(Matrix::new([1, 2, 3, 4, 5, 6], 2, 3).pow(2).is_err());
+        Ok(())
+    }
+
+    //自定义的模 7 整数, 没有实现 Default, 只要实现 Zero 和 One 就可以作为矩阵元素
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Mod7(u8);
+
+    impl std::ops::Add for Mod7 {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Mod7((self.0 + rhs.0) % 7)
+        }
+    }
+
+    impl std::ops::AddAssign for Mod7 {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = *self + rhs;
+        }
+    }
+
+    impl Mul for Mod7 {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self {
+            Mod7((self.0 * rhs.0) % 7)
+        }
+    }
+
+    impl crate::Zero for Mod7 {
+        fn zero() -> Self {
+            Mod7(0)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl crate::One for Mod7 {
+        fn one() -> Self {
+            Mod7(1)
+        }
+    }
+
+    #[test]
+    fn test_matrix_custom_scalar() -> Result<()> {
+        let a = Matrix::new([Mod7(3), Mod7(1), Mod7(0), Mod7(2)], 2, 2);
+        //[[3,1],[0,2]]^2 = [[9,5],[0,4]] mod 7
+        assert_eq!(
+            a.pow(2)?,
+            Matrix::from([[Mod7(2), Mod7(5)], [Mod7(0), Mod7(4)]])
+        );
+        Ok(())
+    }
+
     //直接测试 multiply 方法， 是否返回错误
     #[test]
     fn test_a_can_not_multiply_b() {
@@ -263,4 +703,30 @@ mod tests {
         let c = multiply(&a, &b);
         assert!(c.is_err());
     }
+
+    #[test]
+    fn test_matrix_collection_traits() {
+        let a = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        let b: Matrix<i32> = (0..2)
+            .map(|i| (1..=3).map(|j| i * 3 + j).collect())
+            .collect();
+        assert_eq!(a, b);
+        assert_ne!(a, Matrix::new([1, 2, 3, 4, 5, 6], 3, 2));
+        assert_eq!(a.clone(), a);
+
+        let mut c = a.clone();
+        c.extend([vec![7, 8, 9]]);
+        assert_eq!((c.row(), c.col()), (3, 3));
+        assert_eq!((&c).into_iter().sum::<i32>(), 45);
+        assert_eq!(c.into_iter().last(), Some(9));
+
+        let set = std::collections::HashSet::from([a.clone(), b]);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_matrix_collect_ragged_should_panic() {
+        let _: Matrix<i32> = [vec![1, 2], vec![3]].into_iter().collect();
+    }
 }
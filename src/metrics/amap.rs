@@ -7,6 +7,8 @@ use std::{
     },
 };
 
+use crate::{ConcurrencyError, Result};
+
 #[derive(Debug)]
 pub struct AmapMetrics {
     //这里key 的类型是 &'static str 是因为 监控指标通常在设计初期我们就已经确定我们要监控什么, 所以key是可以预先预知的;
@@ -40,15 +42,43 @@ impl AmapMetrics {
         }
     }
 
-    pub fn inc(&self, key: impl AsRef<str>) -> anyhow::Result<()> {
+    pub fn inc(&self, key: impl AsRef<str>) -> Result<()> {
         let value = self
             .data
             .get(key.as_ref())
-            .ok_or_else(|| anyhow::anyhow!("key {} not found", key.as_ref()))?;
+            .ok_or_else(|| ConcurrencyError::UnknownMetric(key.as_ref().to_string()))?;
         //这里使用 Relaxed , 无需对优先级进行额外处理
         //fetch_add 先读后加, 原子操作
         value.fetch_add(1, Ordering::Relaxed);
-        anyhow::Ok(())
+        Ok(())
+    }
+
+    pub fn dec(&self, key: impl AsRef<str>) -> Result<()> {
+        self.add(key, -1)
+    }
+
+    pub fn add(&self, key: impl AsRef<str>, delta: i64) -> Result<()> {
+        self.atomic(key.as_ref())?
+            .fetch_add(delta, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn set(&self, key: impl AsRef<str>, value: i64) -> Result<()> {
+        self.atomic(key.as_ref())?.store(value, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn get(&self, key: impl AsRef<str>) -> Option<i64> {
+        self.data
+            .get(key.as_ref())
+            .map(|v| v.load(Ordering::Relaxed))
+    }
+
+    //key 是预先注册的, 没有注册的 key 直接报错
+    fn atomic(&self, key: &str) -> Result<&AtomicI64> {
+        self.data
+            .get(key)
+            .ok_or_else(|| ConcurrencyError::UnknownMetric(key.to_string()))
     }
 }
 
@@ -1,9 +1,10 @@
 use std::fmt;
 use std::sync::Arc;
 
-use anyhow::Result;
 use dashmap::DashMap;
 
+use crate::Result;
+
 // metrics 的 data structure
 #[derive(Debug, Clone)]
 pub struct CmapMetrics {
@@ -32,6 +33,25 @@ impl CmapMetrics {
         Ok(())
     }
 
+    pub fn dec(&self, key: impl Into<String>) -> Result<()> {
+        self.add(key, -1)
+    }
+
+    pub fn add(&self, key: impl Into<String>, delta: i64) -> Result<()> {
+        let mut count = self.data.entry(key.into()).or_insert(0);
+        *count += delta;
+        Ok(())
+    }
+
+    pub fn set(&self, key: impl Into<String>, value: i64) -> Result<()> {
+        self.data.insert(key.into(), value);
+        Ok(())
+    }
+
+    pub fn get(&self, key: impl AsRef<str>) -> Option<i64> {
+        self.data.get(key.as_ref()).map(|v| *v)
+    }
+
     //因为使用了 并发安全的DashMap, 所以这里也无需提供snapshot, 需要打印的时候直接打印即可, dashmap内部保证线程安全
     // pub fn snapshot(&self) -> Result<HashMap<String, i64>> {
     //     Ok(self
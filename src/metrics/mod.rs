@@ -3,3 +3,45 @@ mod cmap;
 
 pub use amap::*;
 pub use cmap::*;
+
+use crate::Result;
+
+// CmapMetrics 和 AmapMetrics 共同的写入接口, 这样 multiply 之类的计算可以把观测数据写入任意一种 metrics
+// 需要 Send + Sync, 因为 worker 线程会并发写入
+pub trait Metrics: Send + Sync {
+    //累加 delta, delta 可以为负数(比如 queue depth 出队)
+    fn add(&self, key: &str, delta: i64) -> Result<()>;
+
+    //直接设置成某个值, 用于 gauge 类的指标
+    fn set(&self, key: &str, value: i64) -> Result<()>;
+
+    fn get(&self, key: &str) -> Option<i64>;
+}
+
+impl Metrics for CmapMetrics {
+    fn add(&self, key: &str, delta: i64) -> Result<()> {
+        CmapMetrics::add(self, key, delta)
+    }
+
+    fn set(&self, key: &str, value: i64) -> Result<()> {
+        CmapMetrics::set(self, key, value)
+    }
+
+    fn get(&self, key: &str) -> Option<i64> {
+        CmapMetrics::get(self, key)
+    }
+}
+
+impl Metrics for AmapMetrics {
+    fn add(&self, key: &str, delta: i64) -> Result<()> {
+        AmapMetrics::add(self, key, delta)
+    }
+
+    fn set(&self, key: &str, value: i64) -> Result<()> {
+        AmapMetrics::set(self, key, value)
+    }
+
+    fn get(&self, key: &str) -> Option<i64> {
+        AmapMetrics::get(self, key)
+    }
+}
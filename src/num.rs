@@ -0,0 +1,90 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+};
+
+// 加法单位元, 代替之前用 T::default() 当 0 的做法
+// 自定义的数字类型(定点数, 模运算整数等)的 default 不一定是 0
+pub trait Zero: Sized + Add<Output = Self> {
+    fn zero() -> Self;
+
+    fn is_zero(&self) -> bool;
+}
+
+// 乘法单位元, 单位矩阵和 pow 需要用到
+pub trait One: Sized + Mul<Output = Self> {
+    fn one() -> Self;
+}
+
+// 矩阵和向量元素需要满足的约束, 把之前到处重复的一长串 trait 约束收敛到这里
+// 只要满足这些约束的类型都自动实现了 Scalar, 自定义类型只需要实现 Zero 和 One
+pub trait Scalar: fmt::Debug + Copy + Zero + One + AddAssign + Send + Sync + 'static {}
+
+impl<T> Scalar for T where T: fmt::Debug + Copy + Zero + One + AddAssign + Send + Sync + 'static {}
+
+// 在 Scalar 的基础上支持减法, 除法和取负, 比如浮点数
+pub trait Field: Scalar + Sub<Output = Self> + Div<Output = Self> + Neg<Output = Self> {}
+
+impl<T> Field for T where T: Scalar + Sub<Output = T> + Div<Output = T> + Neg<Output = T> {}
+
+// 实数(浮点数), 求均值, 方差, 范数等需要整数转换和开方
+pub trait Real: Field + PartialOrd {
+    fn from_usize(n: usize) -> Self;
+
+    fn from_f64(v: f64) -> Self;
+
+    fn sqrt(self) -> Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_real {
+    ($($t:ty),*) => {
+        $(
+            impl Real for $t {
+                fn from_usize(n: usize) -> Self {
+                    n as $t
+                }
+
+                fn from_f64(v: f64) -> Self {
+                    v as $t
+                }
+
+                fn sqrt(self) -> Self {
+                    <$t>::sqrt(self)
+                }
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_real!(f32, f64);
+
+macro_rules! impl_num {
+    ($zero:expr, $one:expr; $($t:ty),*) => {
+        $(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == $zero
+                }
+            }
+
+            impl One for $t {
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )*
+    };
+}
+
+impl_num!(0, 1; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_num!(0.0, 1.0; f32, f64);
@@ -0,0 +1,398 @@
+use crate::{pool, ConcurrencyError, Matrix, Result, Scalar, Vector};
+
+// Matrix 上的并行组合子, 和 multiply / 归约一样使用 pool 的 worker
+// 数据按块切分, 每块在一个 worker 中处理, 结果按块的顺序拼接, 输出顺序和单线程一致
+impl<T: Copy + Sync> Matrix<T> {
+    //对每个元素执行 f, 比如激活函数, 阈值化
+    pub fn par_map<U, F>(&self, f: F) -> Result<Matrix<U>>
+    where
+        U: Send,
+        F: Fn(T) -> U + Sync,
+    {
+        let data = self.data();
+        let chunks = pool::run(pool::split(data.len(), 1), |range| {
+            data[range].iter().map(|v| f(*v)).collect::<Vec<_>>()
+        })?;
+        Ok(Matrix::new(
+            chunks.into_iter().flatten().collect::<Vec<_>>(),
+            self.row(),
+            self.col(),
+        ))
+    }
+
+    //两个形状相同的矩阵按元素执行 f, 形状不同时返回错误
+    pub fn par_zip_with<U, V, F>(&self, other: &Matrix<U>, f: F) -> Result<Matrix<V>>
+    where
+        U: Copy + Sync,
+        V: Send,
+        F: Fn(T, U) -> V + Sync,
+    {
+        if self.row() != other.row() || self.col() != other.col() {
+            return Err(ConcurrencyError::ShapeMismatch {
+                op: "Matrix zip",
+                left: (self.row(), self.col()),
+                right: (other.row(), other.col()),
+            });
+        }
+        let (a, b) = (self.data(), other.data());
+        let chunks = pool::run(pool::split(a.len(), 1), |range| {
+            a[range.clone()]
+                .iter()
+                .zip(&b[range])
+                .map(|(x, y)| f(*x, *y))
+                .collect::<Vec<_>>()
+        })?;
+        Ok(Matrix::new(
+            chunks.into_iter().flatten().collect::<Vec<_>>(),
+            self.row(),
+            self.col(),
+        ))
+    }
+
+    //每个块从 init 开始用 f 折叠, 各块的结果再按顺序用 combine 合并
+    //init 会在每个块中使用一次, 所以它需要是 combine 的单位元(比如求和时的 0)
+    pub fn par_fold<A, F, C>(&self, init: A, f: F, combine: C) -> Result<A>
+    where
+        A: Clone + Send + Sync,
+        F: Fn(A, T) -> A + Sync,
+        C: Fn(A, A) -> A,
+    {
+        let data = self.data();
+        let partials = pool::run(pool::split(data.len(), 1), |range| {
+            data[range].iter().fold(init.clone(), |acc, v| f(acc, *v))
+        })?;
+        Ok(partials.into_iter().reduce(combine).unwrap_or(init))
+    }
+
+    //对每一行执行 f, 比如按行 softmax / 归一化
+    //f 返回的每一行长度必须一致, 它就是结果矩阵的列数
+    pub fn par_apply_rows<U, F>(&self, f: F) -> Result<Matrix<U>>
+    where
+        U: Send,
+        F: Fn(&[T]) -> Vec<U> + Sync,
+    {
+        let (data, col) = (self.data(), self.col());
+        let chunks = pool::run(pool::split(self.row(), col), |rows| {
+            rows.map(|i| f(&data[i * col..(i + 1) * col]))
+                .collect::<Vec<_>>()
+        })?;
+        let rows = chunks.into_iter().flatten().collect::<Vec<_>>();
+        let new_col = rows.first().map_or(0, |r| r.len());
+        if let Some(idx) = rows.iter().position(|r| r.len() != new_col) {
+            return Err(ConcurrencyError::LengthMismatch {
+                op: "Matrix apply rows",
+                left: rows[idx].len(),
+                right: new_col,
+            });
+        }
+        Ok(Matrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            self.row(),
+            new_col,
+        ))
+    }
+}
+
+// Vector 上的并行原语, 都是两遍的分块算法:
+// 第一遍每个块独立计算一个汇总值(块内的和 / 满足条件的个数), 在调用线程中按块顺序得到每块的偏移
+// 第二遍每个块带着自己的偏移, 把结果写入输出中属于自己的那一段, 块之间没有共享的可变数据
+impl<T: Copy + Send + Sync> Vector<T> {
+    //包含当前元素的前缀和, out[i] = x[0] op x[1] op ... op x[i]
+    //op 需要满足结合律, 比如 +, max, min; 不需要单位元
+    pub fn par_scan_inclusive<F>(&self, op: F) -> Result<Vector<T>>
+    where
+        F: Fn(T, T) -> T + Sync,
+    {
+        self.par_scan(None, op)
+    }
+
+    //不包含当前元素的前缀和, out[0] = init, out[i] = init op x[0] op ... op x[i - 1]
+    //init 一般是 op 的单位元, 比如求和时的 0
+    pub fn par_scan_exclusive<F>(&self, init: T, op: F) -> Result<Vector<T>>
+    where
+        F: Fn(T, T) -> T + Sync,
+    {
+        self.par_scan(Some(init), op)
+    }
+
+    //init 为 None 时是 inclusive 扫描, 否则是从 init 开始的 exclusive 扫描
+    //exclusive 时 init 作为第一个块的偏移, 第二遍直接写出不包含当前元素的结果, 不需要再在调用线程中遍历一次
+    fn par_scan<F>(&self, init: Option<T>, op: F) -> Result<Vector<T>>
+    where
+        F: Fn(T, T) -> T + Sync,
+    {
+        let exclusive = init.is_some();
+        let ranges = pool::split(self.len(), 1);
+        //第一遍: 每个块的汇总值
+        let totals = pool::run(ranges.clone(), |range| {
+            self[range].iter().copied().reduce(&op)
+        })?;
+        //每个块之前所有元素的汇总, inclusive 时第一个块没有
+        let mut offsets = Vec::with_capacity(totals.len());
+        let mut carry = init;
+        for total in totals {
+            offsets.push(carry);
+            carry = match (carry, total) {
+                (Some(c), Some(t)) => Some(op(c, t)),
+                (c, t) => c.or(t),
+            };
+        }
+        //第二遍: 每个块带着偏移在自己的那一段上做扫描
+        let mut out = self.to_vec();
+        let lens = ranges.iter().map(|r| r.len()).collect::<Vec<_>>();
+        let inputs = split_mut_by(&mut out, &lens)
+            .into_iter()
+            .zip(offsets)
+            .collect::<Vec<_>>();
+        pool::run(inputs, |(chunk, offset)| {
+            let mut acc = offset;
+            for v in chunk.iter_mut() {
+                let next = match acc {
+                    Some(a) => op(a, *v),
+                    None => *v,
+                };
+                *v = match acc {
+                    Some(a) if exclusive => a,
+                    _ => next,
+                };
+                acc = Some(next);
+            }
+        })?;
+        Ok(Vector::new(out))
+    }
+
+    //保留满足 pred 的元素, 顺序不变
+    pub fn par_filter<P>(&self, pred: P) -> Result<Vector<T>>
+    where
+        P: Fn(&T) -> bool + Sync,
+    {
+        let (kept, _) = self.par_split_by(pred, true)?;
+        Ok(Vector::new(kept))
+    }
+
+    //按 pred 分成 (满足, 不满足) 两部分, 两部分内部都保持原来的顺序
+    pub fn par_partition<P>(&self, pred: P) -> Result<(Vector<T>, Vector<T>)>
+    where
+        P: Fn(&T) -> bool + Sync,
+    {
+        let (yes, no) = self.par_split_by(pred, false)?;
+        Ok((Vector::new(yes), Vector::new(no)))
+    }
+
+    //par_filter / par_partition 的公共部分, only_kept 为 true 时不生成不满足的那一部分
+    fn par_split_by<P>(&self, pred: P, only_kept: bool) -> Result<(Vec<T>, Vec<T>)>
+    where
+        P: Fn(&T) -> bool + Sync,
+    {
+        let ranges = pool::split(self.len(), 1);
+        //第一遍: 每个块中满足条件的个数
+        let counts = pool::run(ranges.clone(), |range| {
+            self[range].iter().filter(|v| pred(v)).count()
+        })?;
+        let yes_lens = counts.clone();
+        let no_lens = if only_kept {
+            vec![0; counts.len()]
+        } else {
+            ranges
+                .iter()
+                .zip(&counts)
+                .map(|(r, c)| r.len() - c)
+                .collect()
+        };
+        //输出先用任意元素占位, 第二遍会覆盖每一个位置
+        let placeholder = match self.first() {
+            Some(v) => *v,
+            None => return Ok((vec![], vec![])),
+        };
+        let mut yes = vec![placeholder; yes_lens.iter().sum()];
+        let mut no = vec![placeholder; no_lens.iter().sum()];
+        //第二遍: 每个块把元素写入自己的那一段
+        let inputs = ranges
+            .into_iter()
+            .zip(split_mut_by(&mut yes, &yes_lens))
+            .zip(split_mut_by(&mut no, &no_lens))
+            .collect::<Vec<_>>();
+        pool::run(inputs, |((range, yes), no)| {
+            let (mut y, mut n) = (0, 0);
+            for v in &self[range] {
+                if pred(v) {
+                    yes[y] = *v;
+                    y += 1;
+                } else if !only_kept {
+                    no[n] = *v;
+                    n += 1;
+                }
+            }
+        })?;
+        Ok((yes, no))
+    }
+}
+
+impl<T: Scalar> Vector<T> {
+    pub fn par_sum(&self) -> Result<T> {
+        let partials = pool::run(pool::split(self.len(), 1), |range| {
+            let mut sum = T::zero();
+            for v in &self[range] {
+                sum += *v;
+            }
+            sum
+        })?;
+        let mut sum = T::zero();
+        for partial in partials {
+            sum += partial;
+        }
+        Ok(sum)
+    }
+}
+
+impl<T: Copy + Send + Sync + PartialOrd> Vector<T> {
+    //(最小值, 最大值), 空向量返回 None, 浮点数中的 NaN 会被跳过
+    pub fn par_min_max(&self) -> Result<Option<(T, T)>> {
+        let partials = pool::run(pool::split(self.len(), 1), |range| {
+            self[range]
+                .iter()
+                .filter(|v| v.partial_cmp(v).is_some())
+                .fold(None, |acc, v| merge_min_max(acc, Some((*v, *v))))
+        })?;
+        Ok(partials.into_iter().fold(None, merge_min_max))
+    }
+}
+
+fn merge_min_max<T: Copy + PartialOrd>(a: Option<(T, T)>, b: Option<(T, T)>) -> Option<(T, T)> {
+    match (a, b) {
+        (Some((amin, amax)), Some((bmin, bmax))) => Some((
+            if bmin < amin { bmin } else { amin },
+            if bmax > amax { bmax } else { amax },
+        )),
+        (a, b) => a.or(b),
+    }
+}
+
+// 按 lens 把 out 切成连续的几段, 每段交给一个 worker 写入
+fn split_mut_by<'a, T>(mut out: &'a mut [T], lens: &[usize]) -> Vec<&'a mut [T]> {
+    let mut chunks = Vec::with_capacity(lens.len());
+    for len in lens {
+        let (chunk, rest) = std::mem::take(&mut out).split_at_mut(*len);
+        chunks.push(chunk);
+        out = rest;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_map_and_zip() -> Result<()> {
+        let a = Matrix::new([-1.0, 2.0, -3.0, 4.0], 2, 2);
+        let relu = a.par_map(|v: f64| v.max(0.0))?;
+        assert_eq!(relu.data(), [0.0, 2.0, 0.0, 4.0]);
+
+        let mask = a.par_map(|v| v > 0.0)?;
+        let b = a.par_zip_with(&mask, |v, m| if m { v } else { 0.0 })?;
+        assert_eq!(b.data(), relu.data());
+
+        let c = Matrix::new([1.0, 2.0], 1, 2);
+        assert!(a.par_zip_with(&c, |x, y| x + y).is_err());
+
+        // 闭包 panic 时返回 WorkerFailed, 不会让调用方也 panic
+        let big = Matrix::new(vec![1; 1 << 16], 1 << 8, 1 << 8);
+        let err = big.par_map(|v: i32| -> i32 { panic!("bad value {}", v) });
+        assert!(matches!(err, Err(ConcurrencyError::WorkerFailed { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_fold_is_ordered() -> Result<()> {
+        //足够大, 会被切分到多个 worker
+        let n = 1 << 15;
+        let a = Matrix::new((0..n).map(|v| v as u64).collect::<Vec<_>>(), n, 1);
+        let sum = a.par_fold(0u64, |acc, v| acc + v, |x, y| x + y)?;
+        assert_eq!(sum, (n as u64 - 1) * n as u64 / 2);
+
+        let mapped = a.par_map(|v| v * 2)?;
+        assert!(mapped
+            .data()
+            .iter()
+            .enumerate()
+            .all(|(i, v)| *v == i as u64 * 2));
+
+        //用字符串拼接验证合并顺序
+        let small = Matrix::new([1, 2, 3], 1, 3);
+        let s = small.par_fold(String::new(), |acc, v| acc + &v.to_string(), |x, y| x + &y)?;
+        assert_eq!(s, "123");
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_apply_rows() -> Result<()> {
+        let a = Matrix::new([1.0, 3.0, 2.0, 2.0], 2, 2);
+        let normalized = a.par_apply_rows(|row: &[f64]| {
+            let sum: f64 = row.iter().sum();
+            row.iter().map(|v| v / sum).collect()
+        })?;
+        assert_eq!(normalized.data(), [0.25, 0.75, 0.5, 0.5]);
+
+        let bad = a.par_apply_rows(|row| if row[0] == 1.0 { vec![1.0] } else { vec![] });
+        assert!(bad.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_scan() -> Result<()> {
+        let a = Vector::new([3, 1, 4, 1, 5]);
+        assert_eq!(*a.par_scan_inclusive(|x, y| x + y)?, [3, 4, 8, 9, 14]);
+        assert_eq!(*a.par_scan_exclusive(0, |x, y| x + y)?, [0, 3, 4, 8, 9]);
+        assert_eq!(
+            *a.par_scan_inclusive(|x: i32, y| x.max(y))?,
+            [3, 3, 4, 4, 5]
+        );
+        assert!(Vector::<i32>::new([])
+            .par_scan_inclusive(|x, y| x + y)?
+            .is_empty());
+
+        //足够大, 会被切分到多个 worker, 和单线程的结果比较
+        let n = 100_000;
+        let big = Vector::new((0..n).map(|v| v as u64 % 7).collect::<Vec<_>>());
+        let scan = big.par_scan_inclusive(|x, y| x + y)?;
+        let mut acc = 0;
+        for (i, v) in big.iter().enumerate() {
+            acc += v;
+            assert_eq!(scan[i], acc);
+        }
+        assert_eq!(big.par_sum()?, acc);
+        let exclusive = big.par_scan_exclusive(0, |x, y| x + y)?;
+        assert_eq!(exclusive[0], 0);
+        assert_eq!(exclusive[1..], scan[..n - 1]);
+        //init 不是单位元时, 每个块的偏移都要带上 init
+        let exclusive = big.par_scan_exclusive(10, |x, y| x + y)?;
+        assert_eq!(exclusive[0], 10);
+        assert!((1..n).all(|i| exclusive[i] == scan[i - 1] + 10));
+        assert!(Vector::<i32>::new([])
+            .par_scan_exclusive(0, |x, y| x + y)?
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_filter_partition_min_max() -> Result<()> {
+        let n = 100_000;
+        let big = Vector::new((0..n).collect::<Vec<usize>>());
+        let even = big.par_filter(|v| v % 2 == 0)?;
+        assert_eq!(even.len(), n / 2);
+        assert!(even.iter().enumerate().all(|(i, v)| *v == i * 2));
+
+        let (small, large) = big.par_partition(|v| *v < 10)?;
+        assert_eq!(*small, (0..10).collect::<Vec<_>>());
+        assert_eq!(large.len(), n - 10);
+        assert_eq!(large[0], 10);
+        assert_eq!(big.par_min_max()?, Some((0, n - 1)));
+
+        let f = Vector::new([2.0, f64::NAN, -1.0, 5.0]);
+        assert_eq!(f.par_min_max()?, Some((-1.0, 5.0)));
+        assert_eq!(Vector::<f64>::new([]).par_min_max()?, None);
+        assert!(Vector::<i32>::new([]).par_filter(|_| true)?.is_empty());
+        Ok(())
+    }
+}
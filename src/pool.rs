@@ -0,0 +1,167 @@
+use std::{
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{ConcurrencyError, Result};
+
+//4个线程
+pub(crate) const NUM_THREADS: usize = 4;
+
+// 总工作量(元素个数)小于这个值时不拆分, 直接在当前线程算, 开线程的开销比计算还大
+const PAR_THRESHOLD: usize = 1 << 14;
+
+// 把 0..len 切成最多 NUM_THREADS 个连续区间, 按顺序返回
+// weight 为每一项的工作量, 比如按行切分时传入列数
+pub(crate) fn split(len: usize, weight: usize) -> Vec<Range<usize>> {
+    if len == 0 || len.saturating_mul(weight.max(1)) < PAR_THRESHOLD {
+        return std::iter::once(0..len).collect();
+    }
+    let n = NUM_THREADS.min(len);
+    let size = len.div_ceil(n);
+    (0..n)
+        .map(|i| (i * size).min(len)..((i + 1) * size).min(len))
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+// worker 运行情况的回调, 在 worker 线程中调用, 所以需要 Sync
+// multiply 用它把每个 worker 的 queue depth 和 busy time 写入 metrics
+pub(crate) trait Observer: Sync {
+    //任务放入 worker 的 channel
+    fn queued(&self, _worker: usize) {}
+
+    //worker 从 channel 中取出任务, 开始执行
+    fn started(&self, _worker: usize) {}
+
+    //任务执行完成, elapsed 为执行 f 的耗时
+    fn finished(&self, _worker: usize, _elapsed: Duration) {}
+}
+
+// 什么都不记录
+pub(crate) struct NoopObserver;
+
+impl Observer for NoopObserver {}
+
+pub(crate) fn run<I, O, F>(inputs: Vec<I>, f: F) -> Result<Vec<O>>
+where
+    I: Send,
+    O: Send,
+    F: Fn(I) -> O + Sync,
+{
+    run_observed(inputs, f, &NoopObserver, |_, _| {})
+}
+
+// 和 multiply 一样的 fan-out 方式: 每个 worker 一个 mpsc channel, 每个任务一个 oneshot 返回结果
+// 任务按 idx % NUM_THREADS 分配给 worker, 结果按 inputs 的顺序返回, 保证输出顺序是确定的
+// 使用 thread::scope, f 和 inputs 可以直接借用调用方的数据, 不需要 'static
+// on_result 在调用线程中按 idx 顺序调用, 可以用来汇报进度
+// f panic 时在 worker 中捕获, 返回 WorkerFailed, 而不是让 thread::scope 在调用方再次 panic
+pub(crate) fn run_observed<I, O, F>(
+    inputs: Vec<I>,
+    f: F,
+    observer: &dyn Observer,
+    mut on_result: impl FnMut(usize, &O),
+) -> Result<Vec<O>>
+where
+    I: Send,
+    O: Send,
+    F: Fn(I) -> O + Sync,
+{
+    let call = |worker: usize, input: I| {
+        observer.started(worker);
+        let start = Instant::now();
+        let output = panic::catch_unwind(AssertUnwindSafe(|| f(input)));
+        observer.finished(worker, start.elapsed());
+        output.map_err(|_| ConcurrencyError::WorkerFailed { idx: worker })
+    };
+
+    //只有一个任务时没必要开线程, 直接当成 worker 0 执行
+    if inputs.len() <= 1 {
+        let mut outputs = Vec::with_capacity(inputs.len());
+        for (idx, input) in inputs.into_iter().enumerate() {
+            observer.queued(0);
+            let output = call(0, input)?;
+            on_result(idx, &output);
+            outputs.push(output);
+        }
+        return Ok(outputs);
+    }
+
+    thread::scope(|s| {
+        let call = &call;
+        let senders = (0..NUM_THREADS.min(inputs.len()))
+            .map(|worker| {
+                let (tx, rx) = mpsc::channel::<(I, oneshot::Sender<Result<O>>)>();
+                s.spawn(move || {
+                    for (input, sender) in rx {
+                        if let Err(e) = sender.send(call(worker, input)) {
+                            eprintln!("Send error: {:?}", e);
+                        }
+                    }
+                });
+                tx
+            })
+            .collect::<Vec<_>>();
+
+        let mut receives = Vec::with_capacity(inputs.len());
+        for (idx, input) in inputs.into_iter().enumerate() {
+            let worker = idx % senders.len();
+            let (tx, rx) = oneshot::channel();
+            observer.queued(worker);
+            senders[worker]
+                .send((input, tx))
+                .map_err(|_| ConcurrencyError::ChannelClosed)?;
+            receives.push(rx);
+        }
+        //sender 全部 drop 之后, worker 的 for 循环才会结束, scope 才能退出
+        let workers = senders.len();
+        drop(senders);
+
+        let mut outputs = Vec::with_capacity(receives.len());
+        for (idx, rx) in receives.into_iter().enumerate() {
+            let output = rx
+                .recv()
+                .map_err(|_| ConcurrencyError::WorkerFailed { idx: idx % workers })??;
+            on_result(idx, &output);
+            outputs.push(output);
+        }
+        Ok(outputs)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_and_run() -> Result<()> {
+        assert_eq!(split(10, 1), vec![0..10]);
+        assert_eq!(split(10, 1 << 14), vec![0..3, 3..6, 6..9, 9..10]);
+        assert_eq!(split(2, 1 << 14), vec![0..1, 1..2]);
+
+        let data = (0..100).collect::<Vec<u64>>();
+        let sums = run(split(data.len(), 1 << 14), |r| data[r].iter().sum::<u64>())?;
+        assert_eq!(sums.len(), 4);
+        assert_eq!(sums.iter().sum::<u64>(), 4950);
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_worker_panic() {
+        // 第 5 个任务分配给 worker 5 % 4 = 1
+        let result = run((0..8).collect(), |i| {
+            if i == 5 {
+                panic!("task {} failed", i);
+            }
+            i
+        });
+        assert_eq!(result, Err(ConcurrencyError::WorkerFailed { idx: 1 }));
+        // 只有一个任务时在当前线程执行, 也返回错误
+        let result = run(vec![0], |_| -> i32 { panic!("inline") });
+        assert_eq!(result, Err(ConcurrencyError::WorkerFailed { idx: 0 }));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{multiply, pool, ConcurrencyError, Matrix, Real, Result, Scalar, Vector};
+
+// Matrix 的按行/按列归约和统计
+// 元素较多时按行切分成若干块, 在 pool 的 worker 中并行计算, 再按块的顺序合并, 结果是确定的
+impl<T: Scalar> Matrix<T> {
+    //每一行的和, 长度为 row
+    pub fn sum_rows(&self) -> Result<Vector<T>> {
+        let chunks = pool::run(pool::split(self.row(), self.col()), |rows| {
+            rows.map(|i| sum(self.row_slice(i))).collect::<Vec<_>>()
+        })?;
+        Ok(Vector::new(chunks.concat()))
+    }
+
+    //每一列的和, 长度为 col
+    //每个 worker 计算自己那几行的列和, 最后按块顺序逐列相加
+    pub fn sum_cols(&self) -> Result<Vector<T>> {
+        let partials = pool::run(pool::split(self.row(), self.col()), |rows| {
+            let mut acc = vec![T::zero(); self.col()];
+            for i in rows {
+                for (a, v) in acc.iter_mut().zip(self.row_slice(i)) {
+                    *a += *v;
+                }
+            }
+            acc
+        })?;
+        let mut result = vec![T::zero(); self.col()];
+        for partial in partials {
+            for (a, v) in result.iter_mut().zip(partial) {
+                *a += v;
+            }
+        }
+        Ok(Vector::new(result))
+    }
+
+    //对角线之和, 只有方阵才有 trace
+    pub fn trace(&self) -> Result<T> {
+        if self.row() != self.col() {
+            return Err(ConcurrencyError::NotSquare {
+                op: "Matrix trace",
+                shape: (self.row(), self.col()),
+            });
+        }
+        let mut sum = T::zero();
+        for i in 0..self.row() {
+            sum += self.data()[i * self.col() + i];
+        }
+        Ok(sum)
+    }
+
+    fn row_slice(&self, i: usize) -> &[T] {
+        &self.data()[i * self.col()..(i + 1) * self.col()]
+    }
+}
+
+impl<T: Scalar + PartialOrd> Matrix<T> {
+    //最小值, 空矩阵返回 None, 浮点数中的 NaN 会被跳过
+    pub fn min(&self) -> Result<Option<T>> {
+        Ok(self.arg_by(|a, b| a < b)?.map(|(i, j)| self.get(i, j)))
+    }
+
+    pub fn max(&self) -> Result<Option<T>> {
+        Ok(self.arg_by(|a, b| a > b)?.map(|(i, j)| self.get(i, j)))
+    }
+
+    //最小值所在的 (行, 列), 有多个时返回第一个
+    pub fn argmin(&self) -> Result<Option<(usize, usize)>> {
+        self.arg_by(|a, b| a < b)
+    }
+
+    //最大值所在的 (行, 列), 有多个时返回第一个
+    pub fn argmax(&self) -> Result<Option<(usize, usize)>> {
+        self.arg_by(|a, b| a > b)
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.data()[i * self.col() + j]
+    }
+
+    // better(a, b) 为 true 表示 a 比 b 更好, 每个块先找出自己的最优位置, 再按块的顺序比较
+    // 只有严格更好时才替换, 所以相等时保留的是下标最小的那个
+    fn arg_by(&self, better: impl Fn(&T, &T) -> bool + Sync) -> Result<Option<(usize, usize)>> {
+        let data = self.data();
+        let partials = pool::run(pool::split(data.len(), 1), |range| {
+            // NaN 和任何值比较都是 false, 直接跳过
+            let range = range.filter(|&i| data[i].partial_cmp(&data[i]).is_some());
+            best_of(range, data, &better)
+        })?;
+        let idx = best_of(partials.into_iter().flatten(), data, &better);
+        Ok(idx.map(|idx| (idx / self.col(), idx % self.col())))
+    }
+}
+
+fn best_of<T>(
+    candidates: impl Iterator<Item = usize>,
+    data: &[T],
+    better: &impl Fn(&T, &T) -> bool,
+) -> Option<usize> {
+    candidates.fold(None, |best, idx| match best {
+        Some(b) if !better(&data[idx], &data[b]) => Some(b),
+        _ => Some(idx),
+    })
+}
+
+impl<T: Real> Matrix<T> {
+    //每一行的均值
+    pub fn mean_rows(&self) -> Result<Vector<T>> {
+        let n = T::from_usize(self.col());
+        Ok(Vector::new(
+            self.sum_rows()?.iter().map(|v| *v / n).collect::<Vec<_>>(),
+        ))
+    }
+
+    //每一列的均值
+    pub fn mean_cols(&self) -> Result<Vector<T>> {
+        let n = T::from_usize(self.row());
+        Ok(Vector::new(
+            self.sum_cols()?.iter().map(|v| *v / n).collect::<Vec<_>>(),
+        ))
+    }
+
+    //Frobenius 范数, 所有元素平方和开方
+    pub fn norm(&self) -> Result<T> {
+        let partials = pool::run(pool::split(self.data().len(), 1), |range| {
+            sum_sq(&self.data()[range])
+        })?;
+        Ok(sum(&partials).sqrt())
+    }
+
+    //协方差矩阵, 每一列是一个变量, 每一行是一次观测(和 csv 数据的布局一致)
+    //使用样本协方差, 除以 row - 1, 结果是 col * col 的矩阵
+    //先减去列均值, 再用并行的 multiply 计算 X^T * X
+    pub fn cov(&self) -> Result<Matrix<T>> {
+        if self.row() < 2 {
+            return Err(ConcurrencyError::invalid(
+                "Matrix cov",
+                "need at least 2 rows",
+            ));
+        }
+        let means = self.mean_cols()?;
+        let centered = self
+            .data()
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| *v - means[idx % self.col()])
+            .collect::<Vec<_>>();
+        let centered = Matrix::new(centered, self.row(), self.col());
+        let product = multiply(&centered.transpose(), &centered)?;
+        let n = T::from_usize(self.row() - 1);
+        let data = product.data().iter().map(|v| *v / n).collect::<Vec<_>>();
+        Ok(Matrix::new(data, self.col(), self.col()))
+    }
+
+    //相关系数矩阵, corr[i][j] = cov[i][j] / (std[i] * std[j]), 对角线为 1
+    pub fn corr(&self) -> Result<Matrix<T>> {
+        let cov = self.cov()?;
+        let n = cov.col();
+        let std = (0..n)
+            .map(|i| cov.data()[i * n + i].sqrt())
+            .collect::<Vec<_>>();
+        let data = cov
+            .data()
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| *v / (std[idx / n] * std[idx % n]))
+            .collect::<Vec<_>>();
+        Ok(Matrix::new(data, n, n))
+    }
+}
+
+fn sum<T: Scalar>(data: &[T]) -> T {
+    let mut sum = T::zero();
+    for v in data {
+        sum += *v;
+    }
+    sum
+}
+
+fn sum_sq<T: Scalar>(data: &[T]) -> T {
+    let mut sum = T::zero();
+    for v in data {
+        sum += *v * *v;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_sums() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        assert_eq!(*a.sum_rows()?, [6, 15]);
+        assert_eq!(*a.sum_cols()?, [5, 7, 9]);
+        assert_eq!(Matrix::new([1, 2, 3, 4], 2, 2).trace()?, 5);
+        assert!(a.trace().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_sums_parallel() -> Result<()> {
+        //足够大, 会被切分到多个 worker
+        let (row, col) = (300, 200);
+        let a = Matrix::new(
+            (0..row * col).map(|v| v as u64).collect::<Vec<_>>(),
+            row,
+            col,
+        );
+        let rows = a.sum_rows()?;
+        assert_eq!(rows.len(), row);
+        assert_eq!(rows[1], (col..2 * col).map(|v| v as u64).sum());
+        let cols = a.sum_cols()?;
+        assert_eq!(cols[0], (0..row).map(|i| (i * col) as u64).sum());
+        assert_eq!(a.argmax()?, Some((row - 1, col - 1)));
+        assert_eq!(a.argmin()?, Some((0, 0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_min_max() -> Result<()> {
+        let a = Matrix::new([3, 9, 1, 9, 1, 4], 2, 3);
+        assert_eq!(a.max()?, Some(9));
+        assert_eq!(a.min()?, Some(1));
+        assert_eq!(a.argmax()?, Some((0, 1)));
+        assert_eq!(a.argmin()?, Some((0, 2)));
+        assert_eq!(Matrix::<i32>::new([], 0, 0).max()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_statistics() -> Result<()> {
+        let a = Matrix::new([1.0, 2.0, 2.0, 4.0, 3.0, 6.0], 3, 2);
+        assert_eq!(*a.mean_cols()?, [2.0, 4.0]);
+        assert_eq!(*a.mean_rows()?, [1.5, 3.0, 4.5]);
+        assert!((a.norm()? - 70f64.sqrt()).abs() < 1e-12);
+        assert!(a
+            .cov()?
+            .approx_eq(&Matrix::new([1.0, 2.0, 2.0, 4.0], 2, 2), 1e-12));
+        assert!(a
+            .corr()?
+            .approx_eq(&Matrix::new([1.0, 1.0, 1.0, 1.0], 2, 2), 1e-12));
+        Ok(())
+    }
+}
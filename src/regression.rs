@@ -0,0 +1,181 @@
+use crate::{multiply, ConcurrencyError, Matrix, Real, Result, Vector};
+
+// 判断 R 的对角线是否为 0 时的相对误差
+const RANK_EPSILON: f64 = 1e-12;
+
+impl<T: Real> Matrix<T> {
+    //最小二乘解 argmin ||A x - b||, A 为 m*n (m >= n), b 的长度为 m, 返回长度为 n 的 x
+    //使用 Householder QR 分解, 不直接计算 A^T A, 条件数比正规方程小得多
+    //A 的列线性相关(秩不足)时返回错误
+    pub fn lstsq(&self, b: &Vector<T>) -> Result<Vector<T>> {
+        let (m, n) = (self.row(), self.col());
+        if b.len() != m {
+            return Err(ConcurrencyError::LengthMismatch {
+                op: "Matrix lstsq",
+                left: b.len(),
+                right: m,
+            });
+        }
+        if m < n {
+            return Err(ConcurrencyError::invalid(
+                "Matrix lstsq",
+                format!("underdetermined system {}x{}", m, n),
+            ));
+        }
+
+        let mut a = self.data().to_vec();
+        let mut b = b.to_vec();
+        let scale = self.data().iter().fold(
+            T::zero(),
+            |acc, v| if v.abs() > acc { v.abs() } else { acc },
+        );
+        for k in 0..n {
+            //第 k 列对角线以下的部分构造 Householder 向量 v, H = I - 2 v v^T / (v^T v)
+            let mut v = (k..m).map(|i| a[i * n + k]).collect::<Vec<_>>();
+            let norm = v.iter().fold(T::zero(), |acc, x| acc + *x * *x).sqrt();
+            if norm <= scale * T::from_f64(RANK_EPSILON) {
+                return Err(ConcurrencyError::RankDeficient { column: k });
+            }
+            let alpha = if v[0] > T::zero() { -norm } else { norm };
+            v[0] = v[0] - alpha;
+            let vv = v.iter().fold(T::zero(), |acc, x| acc + *x * *x);
+            let two = T::one() + T::one();
+
+            //把 H 作用到 A 的剩余列和 b 上
+            for j in k..n {
+                let dot = (k..m).fold(T::zero(), |acc, i| acc + v[i - k] * a[i * n + j]);
+                let f = two * dot / vv;
+                for i in k..m {
+                    a[i * n + j] = a[i * n + j] - f * v[i - k];
+                }
+            }
+            let dot = (k..m).fold(T::zero(), |acc, i| acc + v[i - k] * b[i]);
+            let f = two * dot / vv;
+            for i in k..m {
+                b[i] = b[i] - f * v[i - k];
+            }
+        }
+
+        //回代求解上三角方程 R x = Q^T b
+        let mut x = vec![T::zero(); n];
+        for i in (0..n).rev() {
+            let mut sum = b[i];
+            for j in i + 1..n {
+                sum = sum - a[i * n + j] * x[j];
+            }
+            x[i] = sum / a[i * n + i];
+        }
+        Ok(Vector::new(x))
+    }
+}
+
+// 线性回归 y = intercept + x * coefficients
+// 每一行是一个样本, 每一列是一个特征
+#[derive(Debug, Clone)]
+pub struct LinearRegression {
+    pub intercept: f64,
+    pub coefficients: Vec<f64>,
+}
+
+impl LinearRegression {
+    //在 x 前面加一列 1 得到设计矩阵, 再用最小二乘求解
+    pub fn fit(x: &Matrix<f64>, y: &Vector<f64>) -> Result<Self> {
+        let beta = design_matrix(x)?.lstsq(y)?;
+        Ok(Self {
+            intercept: beta[0],
+            coefficients: beta[1..].to_vec(),
+        })
+    }
+
+    //x 的列数必须和训练时的特征数一致
+    pub fn predict(&self, x: &Matrix<f64>) -> Result<Vector<f64>> {
+        if x.col() != self.coefficients.len() {
+            return Err(ConcurrencyError::LengthMismatch {
+                op: "LinearRegression predict",
+                left: x.col(),
+                right: self.coefficients.len(),
+            });
+        }
+        let coef = Matrix::new(self.coefficients.clone(), x.col(), 1);
+        let y = multiply(x, &coef)?.par_map(|v| v + self.intercept)?;
+        Ok(Vector::new(y.data()))
+    }
+
+    //残差 y - predict(x)
+    pub fn residuals(&self, x: &Matrix<f64>, y: &Vector<f64>) -> Result<Vector<f64>> {
+        let predicted = self.predict(x)?;
+        if predicted.len() != y.len() {
+            return Err(ConcurrencyError::LengthMismatch {
+                op: "LinearRegression",
+                left: predicted.len(),
+                right: y.len(),
+            });
+        }
+        Ok(Vector::new(
+            y.iter()
+                .zip(predicted.iter())
+                .map(|(a, b)| a - b)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    //决定系数 R^2 = 1 - SS_res / SS_tot, 1 表示完全拟合
+    pub fn r2(&self, x: &Matrix<f64>, y: &Vector<f64>) -> Result<f64> {
+        let residuals = self.residuals(x, y)?;
+        let mean = y.iter().sum::<f64>() / y.len() as f64;
+        let ss_res: f64 = residuals.iter().map(|r| r * r).sum();
+        let ss_tot: f64 = y.iter().map(|v| (v - mean) * (v - mean)).sum();
+        Ok(1.0 - ss_res / ss_tot)
+    }
+}
+
+// 设计矩阵 [1 | x], 按行并行构造
+fn design_matrix(x: &Matrix<f64>) -> Result<Matrix<f64>> {
+    x.par_apply_rows(|row| {
+        let mut out = Vec::with_capacity(row.len() + 1);
+        out.push(1.0);
+        out.extend_from_slice(row);
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lstsq() -> Result<()> {
+        //超定方程组, 精确解为 x = [1, 2]
+        let a = Matrix::new([1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0], 4, 2);
+        let b = Vector::new([3.0, 5.0, 7.0, 9.0]);
+        let x = a.lstsq(&b)?;
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+
+        let singular = Matrix::new([1.0, 2.0, 2.0, 4.0, 3.0, 6.0], 3, 2);
+        assert!(matches!(
+            singular.lstsq(&Vector::new([1.0, 2.0, 3.0])),
+            Err(ConcurrencyError::RankDeficient { .. })
+        ));
+        assert!(a.lstsq(&Vector::new([1.0])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_regression() -> Result<()> {
+        // y = 1 + 2 * x1 - 3 * x2, 最后一个样本加一点噪声
+        let x = Matrix::new([0.0, 1.0, 1.0, 0.0, 2.0, 1.0, 3.0, 3.0, 4.0, 2.0], 5, 2);
+        let y = Vector::new([-2.0, 3.0, 2.0, -2.0, 3.5]);
+        let model = LinearRegression::fit(&x, &y)?;
+        assert!((model.coefficients[0] - 2.0).abs() < 0.2);
+        assert!((model.coefficients[1] + 3.0).abs() < 0.2);
+
+        let residuals = model.residuals(&x, &y)?;
+        assert_eq!(residuals.len(), 5);
+        assert!(residuals.iter().sum::<f64>().abs() < 1e-9);
+        let r2 = model.r2(&x, &y)?;
+        assert!(r2 > 0.99 && r2 <= 1.0);
+
+        assert!(model.predict(&Matrix::new([1.0], 1, 1)).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{vector::check_len, Accumulator, Result, VectorView};
+
+// 使用 SIMD 指令的点乘, 支持 f32 / f64 / i32
+// 运行时检测 CPU 特性, x86_64 上有 avx / avx2 时走向量化的实现, 否则退回标量循环
+// 注意:
+// - 浮点数按 lane 分组累加, 累加顺序和 Plain 不同, 结果可能在最后几位有差异
+// - i32 的向量乘加是回绕的, 所以标量退回路径也使用 wrapping, 行为和 Wrapping 一致
+// - 只有连续的切片能向量化, 带 stride 的视图走标量循环, 所以 prefers_contiguous 返回 true, multiply 会先转置 b
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Simd;
+
+impl Accumulator<f32> for Simd {
+    type Output = f32;
+
+    fn dot(&self, a: &[f32], b: &[f32]) -> Result<f32> {
+        // 向量化的实现按 a 的长度读取 b, 长度不一致时会越界读, 所以进入 unsafe 之前必须检查
+        check_len(&a, &b, "Simd dot")?;
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx") {
+            // 上面已经检测过 CPU 支持 avx, 这里调用是安全的
+            return Ok(unsafe { x86::dot_f32(a, b) });
+        }
+        Ok(a.iter().zip(b).fold(0.0, |sum, (x, y)| sum + x * y))
+    }
+
+    fn dot_view(&self, a: VectorView<'_, f32>, b: VectorView<'_, f32>) -> Result<f32> {
+        if let (Some(a), Some(b)) = (a.as_slice(), b.as_slice()) {
+            return self.dot(a, b);
+        }
+        check_len(&a, &b, "Simd dot")?;
+        Ok(a.iter()
+            .zip(b.iter())
+            .fold(0.0, |sum: f32, (x, y)| sum + x * y))
+    }
+
+    fn prefers_contiguous(&self) -> bool {
+        true
+    }
+}
+
+impl Accumulator<f64> for Simd {
+    type Output = f64;
+
+    fn dot(&self, a: &[f64], b: &[f64]) -> Result<f64> {
+        check_len(&a, &b, "Simd dot")?;
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx") {
+            return Ok(unsafe { x86::dot_f64(a, b) });
+        }
+        Ok(a.iter().zip(b).fold(0.0, |sum, (x, y)| sum + x * y))
+    }
+
+    fn dot_view(&self, a: VectorView<'_, f64>, b: VectorView<'_, f64>) -> Result<f64> {
+        if let (Some(a), Some(b)) = (a.as_slice(), b.as_slice()) {
+            return self.dot(a, b);
+        }
+        check_len(&a, &b, "Simd dot")?;
+        Ok(a.iter()
+            .zip(b.iter())
+            .fold(0.0, |sum: f64, (x, y)| sum + x * y))
+    }
+
+    fn prefers_contiguous(&self) -> bool {
+        true
+    }
+}
+
+impl Accumulator<i32> for Simd {
+    type Output = i32;
+
+    fn dot(&self, a: &[i32], b: &[i32]) -> Result<i32> {
+        check_len(&a, &b, "Simd dot")?;
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            return Ok(unsafe { x86::dot_i32(a, b) });
+        }
+        Ok(a.iter()
+            .zip(b)
+            .fold(0, |sum: i32, (x, y)| sum.wrapping_add(x.wrapping_mul(*y))))
+    }
+
+    fn dot_view(&self, a: VectorView<'_, i32>, b: VectorView<'_, i32>) -> Result<i32> {
+        if let (Some(a), Some(b)) = (a.as_slice(), b.as_slice()) {
+            return self.dot(a, b);
+        }
+        check_len(&a, &b, "Simd dot")?;
+        Ok(a.iter()
+            .zip(b.iter())
+            .fold(0, |sum: i32, (x, y)| sum.wrapping_add(x.wrapping_mul(*y))))
+    }
+
+    fn prefers_contiguous(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    // 每次处理 8 个 f32 (256 bit), 剩下不足 8 个的部分用标量循环处理
+    // 调用方需要保证 CPU 支持 avx, 并且 a.len() == b.len()
+    #[target_feature(enable = "avx")]
+    pub(super) unsafe fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+        let n = a.len() / 8 * 8;
+        let mut acc = _mm256_setzero_ps();
+        for i in (0..n).step_by(8) {
+            let x = _mm256_loadu_ps(a.as_ptr().add(i));
+            let y = _mm256_loadu_ps(b.as_ptr().add(i));
+            acc = _mm256_add_ps(acc, _mm256_mul_ps(x, y));
+        }
+        let mut lanes = [0f32; 8];
+        _mm256_storeu_ps(lanes.as_mut_ptr(), acc);
+        let mut sum: f32 = lanes.iter().sum();
+        for i in n..a.len() {
+            sum += a[i] * b[i];
+        }
+        sum
+    }
+
+    // 每次处理 4 个 f64
+    #[target_feature(enable = "avx")]
+    pub(super) unsafe fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() / 4 * 4;
+        let mut acc = _mm256_setzero_pd();
+        for i in (0..n).step_by(4) {
+            let x = _mm256_loadu_pd(a.as_ptr().add(i));
+            let y = _mm256_loadu_pd(b.as_ptr().add(i));
+            acc = _mm256_add_pd(acc, _mm256_mul_pd(x, y));
+        }
+        let mut lanes = [0f64; 4];
+        _mm256_storeu_pd(lanes.as_mut_ptr(), acc);
+        let mut sum: f64 = lanes.iter().sum();
+        for i in n..a.len() {
+            sum += a[i] * b[i];
+        }
+        sum
+    }
+
+    // 每次处理 8 个 i32, 整数的乘法和加法需要 avx2
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn dot_i32(a: &[i32], b: &[i32]) -> i32 {
+        let n = a.len() / 8 * 8;
+        let mut acc = _mm256_setzero_si256();
+        for i in (0..n).step_by(8) {
+            let x = _mm256_loadu_si256(a.as_ptr().add(i) as *const __m256i);
+            let y = _mm256_loadu_si256(b.as_ptr().add(i) as *const __m256i);
+            acc = _mm256_add_epi32(acc, _mm256_mullo_epi32(x, y));
+        }
+        let mut lanes = [0i32; 8];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, acc);
+        let mut sum = lanes.iter().fold(0i32, |s, v| s.wrapping_add(*v));
+        for i in n..a.len() {
+            sum = sum.wrapping_add(a[i].wrapping_mul(b[i]));
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConcurrencyError, Plain, Wrapping};
+    use rand::Rng;
+
+    #[test]
+    fn test_simd_matches_scalar() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        // 长度不是 8 的倍数, 覆盖尾部的标量处理
+        let n = 1003;
+
+        let a: Vec<i32> = (0..n).map(|_| rng.gen()).collect();
+        let b: Vec<i32> = (0..n).map(|_| rng.gen()).collect();
+        assert_eq!(Simd.dot(&a, &b)?, Wrapping.dot(&a, &b)?);
+
+        let a: Vec<f64> = (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let b: Vec<f64> = (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        assert!((Simd.dot(&a, &b)? - Plain.dot(&a, &b)?).abs() < 1e-9);
+
+        let a: Vec<f32> = a.iter().map(|v| *v as f32).collect();
+        let b: Vec<f32> = b.iter().map(|v| *v as f32).collect();
+        assert!((Simd.dot(&a, &b)? - Plain.dot(&a, &b)?).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simd_length_mismatch() {
+        let err = ConcurrencyError::LengthMismatch {
+            op: "Simd dot",
+            left: 64,
+            right: 1,
+        };
+        assert_eq!(Simd.dot(&[1.0f32; 64], &[1.0f32; 1]), Err(err.clone()));
+        assert_eq!(Simd.dot(&[1.0f64; 64], &[1.0f64; 1]), Err(err.clone()));
+        assert_eq!(Simd.dot(&[1i32; 64], &[1i32; 1]), Err(err));
+    }
+}
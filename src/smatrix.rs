@@ -0,0 +1,169 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut, Mul},
+};
+
+use crate::{multiply, ConcurrencyError, Matrix, Result, Scalar};
+
+// 行列数都不超过这个值时, 直接在当前线程用常量边界的循环计算, 编译器会把循环完全展开
+// 2x2, 3x3, 4x4 这样的小矩阵走多线程反而更慢
+const UNROLL_LIMIT: usize = 4;
+
+// 编译期确定大小的矩阵, R 行 C 列, 数据直接放在数组里
+// 和 Matrix 不同, 形状是类型的一部分: SMatrix<T, R, C> * SMatrix<T, C, K> 才能通过编译,
+// 不会像 multiply 那样到运行时才发现 a.col != b.row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SMatrix<T, const R: usize, const C: usize> {
+    data: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub fn new(data: [[T; C]; R]) -> Self {
+        Self { data }
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub fn zeros() -> Self {
+        Self::new([[T::zero(); C]; R])
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R> {
+        SMatrix::new(std::array::from_fn(|j| {
+            std::array::from_fn(|i| self.data[i][j])
+        }))
+    }
+}
+
+impl<T: Scalar, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> Self {
+        let mut m = Self::zeros();
+        for i in 0..N {
+            m.data[i][i] = T::one();
+        }
+        m
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i][j]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i][j]
+    }
+}
+
+// 内层维度 C 必须一致, 结果是 R 行 K 列
+impl<T, const R: usize, const C: usize, const K: usize> Mul<SMatrix<T, C, K>> for SMatrix<T, R, C>
+where
+    T: Scalar,
+{
+    type Output = SMatrix<T, R, K>;
+
+    fn mul(self, rhs: SMatrix<T, C, K>) -> Self::Output {
+        if R <= UNROLL_LIMIT && C <= UNROLL_LIMIT && K <= UNROLL_LIMIT {
+            return mul_unrolled(&self, &rhs);
+        }
+        //大矩阵走多线程的 multiply, 形状已经由类型保证, 这里不会出错
+        let c = multiply(&Matrix::from(self), &Matrix::from(rhs)).expect("Matrix multiply error");
+        SMatrix::try_from(c).expect("Matrix shape error")
+    }
+}
+
+// R, C, K 都是常量, 小尺寸下编译器会把三层循环完全展开
+fn mul_unrolled<T: Scalar, const R: usize, const C: usize, const K: usize>(
+    a: &SMatrix<T, R, C>,
+    b: &SMatrix<T, C, K>,
+) -> SMatrix<T, R, K> {
+    let mut out = SMatrix::zeros();
+    for i in 0..R {
+        for j in 0..K {
+            let mut sum = T::zero();
+            for k in 0..C {
+                sum += a.data[i][k] * b.data[k][j];
+            }
+            out.data[i][j] = sum;
+        }
+    }
+    out
+}
+
+impl<T: Scalar, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(m: SMatrix<T, R, C>) -> Self {
+        Matrix::new(m.data.concat(), R, C)
+    }
+}
+
+// 动态矩阵的形状只能在运行时检查, 不一致时返回错误
+impl<T: Scalar, const R: usize, const C: usize> TryFrom<Matrix<T>> for SMatrix<T, R, C> {
+    type Error = ConcurrencyError;
+
+    fn try_from(m: Matrix<T>) -> Result<Self> {
+        if m.row() != R || m.col() != C {
+            return Err(ConcurrencyError::ShapeMismatch {
+                op: "SMatrix",
+                left: (m.row(), m.col()),
+                right: (R, C),
+            });
+        }
+        let data = m.data();
+        Ok(Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| data[i * C + j])
+        })))
+    }
+}
+
+// 和 Matrix 的显示格式一致
+impl<T, const R: usize, const C: usize> fmt::Display for SMatrix<T, R, C>
+where
+    T: fmt::Display + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&Matrix::new(self.data.concat(), R, C), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smatrix_multiply() {
+        let a = SMatrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b = SMatrix::new([[1, 2], [3, 4], [5, 6]]);
+        let c: SMatrix<i32, 2, 2> = a * b;
+        assert_eq!(c, SMatrix::new([[22, 28], [49, 64]]));
+        assert_eq!(c.to_string(), "[22 28]\n[49 64]");
+        assert_eq!(c * SMatrix::identity(), c);
+        assert_eq!(a.transpose(), SMatrix::new([[1, 4], [2, 5], [3, 6]]));
+    }
+
+    #[test]
+    fn test_smatrix_large_matches_matrix() -> Result<()> {
+        let a: SMatrix<i64, 6, 5> = SMatrix::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (i * 5 + j) as i64)
+        }));
+        let b = a.transpose();
+        let expected = multiply(&Matrix::from(a), &Matrix::from(b))?;
+        let c = a * b;
+        assert_eq!(Matrix::from(c).data(), expected.data());
+        Ok(())
+    }
+
+    #[test]
+    fn test_smatrix_conversion() {
+        let m = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        let s: SMatrix<i32, 2, 3> = m.try_into().unwrap();
+        assert_eq!(s[(1, 2)], 6);
+        assert_eq!(Matrix::from(s).data(), [1, 2, 3, 4, 5, 6]);
+
+        let m = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3);
+        assert!(SMatrix::<i32, 3, 2>::try_from(m).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+
+use crate::{pool, ConcurrencyError, Result, Vector};
+
+// Vector 的并行排序, 返回排好序的新 Vector, 原来的不变
+// 并行归并排序: 按 pool::split 切块, 每块在 worker 中用 slice 的排序, 再一轮一轮地两两归并相邻的块
+// 归并时相等的元素总是先取左边的块, 所以 par_sort / par_sort_by_key 和 slice::sort 一样是稳定的
+impl<T: Clone + Send + Sync> Vector<T> {
+    pub fn par_sort(&self) -> Result<Vector<T>>
+    where
+        T: Ord,
+    {
+        par_sort_by(self, T::cmp, true)
+    }
+
+    // 按 key 稳定排序, 结果和 slice::sort_by_key 完全一致
+    pub fn par_sort_by_key<K, F>(&self, f: F) -> Result<Vector<T>>
+    where
+        K: Ord,
+        F: Fn(&T) -> K + Sync,
+    {
+        par_sort_by(self, |a, b| f(a).cmp(&f(b)), true)
+    }
+
+    // 不保证相等元素的顺序, 块内用 sort_unstable, 更快一些
+    pub fn par_sort_unstable(&self) -> Result<Vector<T>>
+    where
+        T: Ord,
+    {
+        par_sort_by(self, T::cmp, false)
+    }
+}
+
+impl<T: Clone + Send + Sync + Ord> Vector<T> {
+    // 最大的 k 个元素, 从大到小排列, k 超过长度时返回全部
+    // 每个块先选出自己最大的 k 个, 再在候选中选, 不需要对整个向量排序
+    pub fn top_k(&self, k: usize) -> Result<Vector<T>> {
+        let candidates = pool::run(pool::split(self.len(), 1), |range| {
+            let mut chunk = self[range].to_vec();
+            largest(&mut chunk, k);
+            chunk
+        })?;
+        let mut all = candidates.concat();
+        largest(&mut all, k);
+        all.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Vector::new(all))
+    }
+
+    // 从小到大排序后下标为 n 的元素, quickselect, 平均 O(n), n 越界时返回错误
+    pub fn select_nth(&self, n: usize) -> Result<T> {
+        if n >= self.len() {
+            return Err(ConcurrencyError::OutOfBounds {
+                op: "Vector select_nth",
+                index: n,
+                len: self.len(),
+            });
+        }
+        let mut data = self.to_vec();
+        data.select_nth_unstable(n);
+        Ok(data.swap_remove(n))
+    }
+}
+
+// 只保留最大的 k 个, 顺序不确定
+fn largest<T: Ord>(data: &mut Vec<T>, k: usize) {
+    if k == 0 {
+        data.clear();
+    } else if k < data.len() {
+        data.select_nth_unstable_by(k - 1, |a, b| b.cmp(a));
+        data.truncate(k);
+    }
+}
+
+fn par_sort_by<T, F>(data: &[T], cmp: F, stable: bool) -> Result<Vector<T>>
+where
+    T: Clone + Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let mut chunks = pool::run(pool::split(data.len(), 1), |range| {
+        let mut chunk = data[range].to_vec();
+        if stable {
+            chunk.sort_by(&cmp);
+        } else {
+            chunk.sort_unstable_by(&cmp);
+        }
+        chunk
+    })?;
+    //每一轮把相邻的两块归并成一块, 块的相对顺序不变
+    while chunks.len() > 1 {
+        let mut pairs = Vec::with_capacity(chunks.len().div_ceil(2));
+        let mut iter = chunks.into_iter();
+        while let Some(left) = iter.next() {
+            pairs.push((left, iter.next()));
+        }
+        chunks = pool::run(pairs, |(left, right)| match right {
+            Some(right) => merge(left, right, &cmp),
+            None => left,
+        })?;
+    }
+    Ok(Vector::new(chunks.pop().unwrap_or_default()))
+}
+
+// 相等时先取 left, 保证稳定; 元素直接 move 到结果中, 不需要 Copy 或 Clone
+fn merge<T>(left: Vec<T>, right: Vec<T>, cmp: impl Fn(&T, &T) -> Ordering) -> Vec<T> {
+    let mut out = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let next = if cmp(r, l) == Ordering::Less {
+            right.next()
+        } else {
+            left.next()
+        };
+        out.extend(next);
+    }
+    out.extend(left);
+    out.extend(right);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 简单的伪随机数, 测试数据每次都一样
+    fn lcg(n: usize, seed: u64) -> Vec<u64> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                x = x
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                x >> 33
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_par_sort_matches_slice_sort() -> Result<()> {
+        //足够大, 会被切分到多个 worker
+        let data = lcg(100_000, 42);
+        let v = Vector::new(data.clone());
+        let mut expected = data.clone();
+        expected.sort();
+        assert_eq!(*v.par_sort()?, expected);
+        assert_eq!(*v.par_sort_unstable()?, expected);
+        assert!(Vector::<i32>::new([]).par_sort()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_sort_by_key_is_stable() -> Result<()> {
+        // 只按低 4 位排序, 相同 key 的元素很多, 稳定排序要保持它们原来的顺序
+        let data = lcg(100_000, 7)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (v % 16, i))
+            .collect::<Vec<_>>();
+        let v = Vector::new(data.clone());
+        let mut expected = data;
+        expected.sort_by_key(|(k, _)| *k);
+        assert_eq!(*v.par_sort_by_key(|(k, _)| *k)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_k_and_select_nth() -> Result<()> {
+        let data = lcg(100_000, 3);
+        let v = Vector::new(data.clone());
+        let mut sorted = data;
+        sorted.sort();
+
+        let top = v.top_k(5)?;
+        let expected = sorted.iter().rev().take(5).copied().collect::<Vec<_>>();
+        assert_eq!(*top, expected);
+        assert!(v.top_k(0)?.is_empty());
+        assert_eq!(Vector::new([3, 1, 2]).top_k(10)?.to_vec(), [3, 2, 1]);
+
+        assert_eq!(v.select_nth(0)?, sorted[0]);
+        assert_eq!(v.select_nth(50_000)?, sorted[50_000]);
+        assert!(v.select_nth(100_000).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_non_copy() -> Result<()> {
+        // String 不是 Copy, 结果和 slice::sort 一致
+        let data = lcg(50_000, 11)
+            .into_iter()
+            .map(|v| format!("item-{}", v % 1000))
+            .collect::<Vec<_>>();
+        let v = Vector::new(data.clone());
+        let mut expected = data.clone();
+        expected.sort();
+        assert_eq!(*v.par_sort()?, expected);
+        let mut by_len = data.clone();
+        by_len.sort_by_key(|s| s.len());
+        assert_eq!(*v.par_sort_by_key(|s| s.len())?, by_len);
+        assert_eq!(v.select_nth(0)?, expected[0]);
+        assert_eq!(v.top_k(1)?[0], expected[expected.len() - 1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{AsVectorView, ConcurrencyError, Result, Scalar, Vector};
+
+// 稀疏向量, 只保存非零元素, 适合几百万维里只有几个非零值的特征向量
+// indices 严格递增, values 和 indices 一一对应, 不保存 0
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseVector<T> {
+    dim: usize,
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Scalar> SparseVector<T> {
+    // 空向量, 所有元素都是 0
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            dim,
+            indices: vec![],
+            values: vec![],
+        }
+    }
+
+    // 由 (下标, 值) 构造, 顺序任意, 值为 0 的会被丢掉
+    // 下标越界或者重复时返回错误
+    pub fn new(dim: usize, entries: impl IntoIterator<Item = (usize, T)>) -> Result<Self> {
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(i, _)| *i);
+        for pair in entries.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(ConcurrencyError::invalid(
+                    "SparseVector",
+                    format!("duplicate index {}", pair[0].0),
+                ));
+            }
+        }
+        if let Some((i, _)) = entries.last() {
+            if *i >= dim {
+                return Err(ConcurrencyError::OutOfBounds {
+                    op: "SparseVector",
+                    index: *i,
+                    len: dim,
+                });
+            }
+        }
+        let (indices, values) = entries.into_iter().filter(|(_, v)| !v.is_zero()).unzip();
+        Ok(Self {
+            dim,
+            indices,
+            values,
+        })
+    }
+
+    pub fn from_dense(v: &impl AsVectorView<T>) -> Self {
+        let v = v.as_view();
+        let (indices, values) = v
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(i, v)| (i, *v))
+            .unzip();
+        Self {
+            dim: v.len(),
+            indices,
+            values,
+        }
+    }
+
+    pub fn to_dense(&self) -> Vector<T> {
+        let mut data = vec![T::zero(); self.dim];
+        for (i, v) in self.iter() {
+            data[i] = *v;
+        }
+        Vector::new(data)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    // 非零元素的个数
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    // 按下标递增的顺序遍历非零元素
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.indices.iter().copied().zip(self.values.iter())
+    }
+
+    // 下标 i 处的值, 没有保存的就是 0, 越界返回 None
+    pub fn get(&self, i: usize) -> Option<T> {
+        if i >= self.dim {
+            return None;
+        }
+        match self.indices.binary_search(&i) {
+            Ok(pos) => Some(self.values[pos]),
+            Err(_) => Some(T::zero()),
+        }
+    }
+
+    // sparse · sparse, 两个有序下标列表归并, 只有两边都非零的位置才相乘
+    // 复杂度 O(nnz(a) + nnz(b)), 和 dim 无关
+    pub fn dot(&self, rhs: &SparseVector<T>) -> Result<T> {
+        self.check_dim(rhs.dim, "SparseVector dot")?;
+        let (mut i, mut j) = (0, 0);
+        let mut sum = T::zero();
+        while i < self.nnz() && j < rhs.nnz() {
+            match self.indices[i].cmp(&rhs.indices[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    sum += self.values[i] * rhs.values[j];
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        Ok(sum)
+    }
+
+    // sparse · dense, 只访问 dense 中对应非零下标的元素, 复杂度 O(nnz)
+    pub fn dot_dense(&self, rhs: &impl AsVectorView<T>) -> Result<T> {
+        let rhs = rhs.as_view();
+        self.check_dim(rhs.len(), "SparseVector dot")?;
+        let mut sum = T::zero();
+        for (i, v) in self.iter() {
+            if let Some(d) = rhs.get(i) {
+                sum += *v * *d;
+            }
+        }
+        Ok(sum)
+    }
+
+    pub fn try_add(&self, rhs: &SparseVector<T>) -> Result<SparseVector<T>> {
+        self.check_dim(rhs.dim, "SparseVector add")?;
+        Ok(self.union_with(rhs, |a, b| a + b, |a| a, |b| b))
+    }
+
+    pub fn try_sub(&self, rhs: &SparseVector<T>) -> Result<SparseVector<T>>
+    where
+        T: Sub<Output = T> + Neg<Output = T>,
+    {
+        self.check_dim(rhs.dim, "SparseVector sub")?;
+        Ok(self.union_with(rhs, |a, b| a - b, |a| a, |b| -b))
+    }
+
+    // 按元素相乘, 结果的非零位置是两边非零位置的交集
+    pub fn hadamard(&self, rhs: &SparseVector<T>) -> Result<SparseVector<T>> {
+        self.check_dim(rhs.dim, "SparseVector hadamard")?;
+        let mut result = Self::zeros(self.dim);
+        let (mut i, mut j) = (0, 0);
+        while i < self.nnz() && j < rhs.nnz() {
+            match self.indices[i].cmp(&rhs.indices[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    result.push(self.indices[i], self.values[i] * rhs.values[j]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    // 乘以标量, k 为 0 时结果是空向量
+    pub fn scale(&self, k: T) -> SparseVector<T> {
+        let mut result = Self::zeros(self.dim);
+        for (i, v) in self.iter() {
+            result.push(i, *v * k);
+        }
+        result
+    }
+
+    // 归并两个有序列表, 只有一边有值的位置用 left / right 处理
+    fn union_with(
+        &self,
+        rhs: &SparseVector<T>,
+        both: impl Fn(T, T) -> T,
+        left: impl Fn(T) -> T,
+        right: impl Fn(T) -> T,
+    ) -> SparseVector<T> {
+        let mut result = Self::zeros(self.dim);
+        let (mut i, mut j) = (0, 0);
+        while i < self.nnz() || j < rhs.nnz() {
+            let a = self.indices.get(i).copied().unwrap_or(usize::MAX);
+            let b = rhs.indices.get(j).copied().unwrap_or(usize::MAX);
+            match a.cmp(&b) {
+                Ordering::Less => {
+                    result.push(a, left(self.values[i]));
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    result.push(b, right(rhs.values[j]));
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    result.push(a, both(self.values[i], rhs.values[j]));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        result
+    }
+
+    // 调用方保证 idx 递增, 相加抵消成 0 的不保存
+    fn push(&mut self, idx: usize, v: T) {
+        if !v.is_zero() {
+            self.indices.push(idx);
+            self.values.push(v);
+        }
+    }
+
+    fn check_dim(&self, dim: usize, op: &'static str) -> Result<()> {
+        if self.dim != dim {
+            return Err(ConcurrencyError::LengthMismatch {
+                op,
+                left: self.dim,
+                right: dim,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<T: Scalar> From<&Vector<T>> for SparseVector<T> {
+    fn from(v: &Vector<T>) -> Self {
+        Self::from_dense(v)
+    }
+}
+
+impl<T: Scalar> From<&SparseVector<T>> for Vector<T> {
+    fn from(v: &SparseVector<T>) -> Self {
+        v.to_dense()
+    }
+}
+
+// 运算符版本在维度不一致时 panic, 和 Vector 一样
+impl<T: Scalar> Add for &SparseVector<T> {
+    type Output = SparseVector<T>;
+
+    fn add(self, rhs: Self) -> SparseVector<T> {
+        self.try_add(rhs).expect("SparseVector add error")
+    }
+}
+
+impl<T: Scalar + Sub<Output = T> + Neg<Output = T>> Sub for &SparseVector<T> {
+    type Output = SparseVector<T>;
+
+    fn sub(self, rhs: Self) -> SparseVector<T> {
+        self.try_sub(rhs).expect("SparseVector sub error")
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Neg for &SparseVector<T> {
+    type Output = SparseVector<T>;
+
+    fn neg(self) -> SparseVector<T> {
+        SparseVector {
+            dim: self.dim,
+            indices: self.indices.clone(),
+            values: self.values.iter().map(|v| -*v).collect(),
+        }
+    }
+}
+
+impl<T: Scalar> Mul<T> for &SparseVector<T> {
+    type Output = SparseVector<T>;
+
+    fn mul(self, k: T) -> SparseVector<T> {
+        self.scale(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dot;
+
+    #[test]
+    fn test_sparse_vector_construct() -> Result<()> {
+        let v = SparseVector::new(1_000_000, [(500_000, 2), (3, 1), (7, 0)])?;
+        assert_eq!(v.nnz(), 2);
+        assert_eq!(v.indices(), [3, 500_000]);
+        assert_eq!(v.values(), [1, 2]);
+        assert_eq!(v.get(500_000), Some(2));
+        assert_eq!(v.get(4), Some(0));
+        assert_eq!(v.get(1_000_000), None);
+        assert!(SparseVector::new(10, [(1, 1), (1, 2)]).is_err());
+        assert!(SparseVector::new(10, [(10, 1)]).is_err());
+
+        let dense = Vector::new([0, 4, 0, 0, 5]);
+        let s = SparseVector::from(&dense);
+        assert_eq!(s.indices(), [1, 4]);
+        assert_eq!(*Vector::from(&s), *dense);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_vector_dot() -> Result<()> {
+        let dim = 5_000_000;
+        let a = SparseVector::new(dim, [(1, 2), (10, 3), (4_000_000, 4)])?;
+        let b = SparseVector::new(dim, [(10, 5), (20, 7), (4_000_000, 1)])?;
+        assert_eq!(a.dot(&b)?, 19);
+
+        let a = SparseVector::new(4, [(0, 2), (3, 3)])?;
+        let dense = Vector::new([1, 2, 3, 4]);
+        assert_eq!(a.dot_dense(&dense)?, 14);
+        assert_eq!(a.dot_dense(&dense)?, dot(&a.to_dense(), &dense)?);
+        assert!(a.dot_dense(&[1, 2]).is_err());
+        assert!(a.dot(&SparseVector::zeros(5)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_vector_ops() -> Result<()> {
+        let a = SparseVector::new(6, [(0, 1), (2, 2), (5, 3)])?;
+        let b = SparseVector::new(6, [(2, -2), (3, 4)])?;
+        let sum = &a + &b;
+        // 下标 2 相加为 0, 不再保存
+        assert_eq!(sum.indices(), [0, 3, 5]);
+        assert_eq!(sum.values(), [1, 4, 3]);
+        let diff = &a - &b;
+        assert_eq!(*diff.to_dense(), [1, 0, 4, -4, 0, 3]);
+        assert_eq!(a.hadamard(&b)?.indices(), [2]);
+        assert_eq!(a.scale(0).nnz(), 0);
+        assert_eq!((-&a).values(), [-1, -2, -3]);
+        assert_eq!((&a * 2).values(), [2, 4, 6]);
+        Ok(())
+    }
+}
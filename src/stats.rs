@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+
+use crate::{pool, ConcurrencyError, Real, Result, Vector};
+
+// 单遍计算均值和方差的 Welford 算法, 比先求和再求平方和数值上更稳定
+// 两个 Welford 可以合并(Chan 的公式), 所以每个 worker 算一块, 最后按块的顺序合并
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Welford<T> {
+    pub count: usize,
+    pub mean: T,
+    // 与均值之差的平方和
+    pub m2: T,
+}
+
+impl<T: Real> Default for Welford<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Real> Welford<T> {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+
+    pub fn push(&mut self, v: T) {
+        self.count += 1;
+        let delta = v - self.mean;
+        self.mean += delta / T::from_usize(self.count);
+        self.m2 += delta * (v - self.mean);
+    }
+
+    pub fn merge(&self, other: &Welford<T>) -> Welford<T> {
+        if self.count == 0 {
+            return *other;
+        }
+        if other.count == 0 {
+            return *self;
+        }
+        let count = self.count + other.count;
+        let (na, nb, n) = (
+            T::from_usize(self.count),
+            T::from_usize(other.count),
+            T::from_usize(count),
+        );
+        let delta = other.mean - self.mean;
+        Welford {
+            count,
+            mean: self.mean + delta * nb / n,
+            m2: self.m2 + other.m2 + delta * delta * na * nb / n,
+        }
+    }
+
+    // 样本方差, 除以 count - 1, 和 Matrix::cov 一致
+    pub fn variance(&self) -> Option<T> {
+        if self.count < 2 {
+            return None;
+        }
+        Some(self.m2 / T::from_usize(self.count - 1))
+    }
+}
+
+// histogram 的结果, edges 比 counts 多一个, 第 i 个桶是 [edges[i], edges[i + 1])
+// 最后一个桶包含右边界 (和 numpy.histogram 一致)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram<T> {
+    pub edges: Vec<T>,
+    pub counts: Vec<usize>,
+}
+
+// Vector 的描述统计, 元素较多时按块并行计算
+impl<T: Real> Vector<T> {
+    pub fn moments(&self) -> Result<Welford<T>> {
+        let partials = pool::run(pool::split(self.len(), 1), |range| {
+            let mut w = Welford::new();
+            for v in &self[range] {
+                w.push(*v);
+            }
+            w
+        })?;
+        Ok(partials.iter().fold(Welford::new(), |acc, w| acc.merge(w)))
+    }
+
+    //均值, 空向量返回错误
+    pub fn mean(&self) -> Result<T> {
+        let w = self.moments()?;
+        if w.count == 0 {
+            return Err(ConcurrencyError::invalid("Vector mean", "empty vector"));
+        }
+        Ok(w.mean)
+    }
+
+    //样本方差, 少于 2 个元素时返回错误
+    pub fn variance(&self) -> Result<T> {
+        self.moments()?
+            .variance()
+            .ok_or_else(|| ConcurrencyError::invalid("Vector variance", "need at least 2 elements"))
+    }
+
+    pub fn stddev(&self) -> Result<T> {
+        Ok(self.variance()?.sqrt())
+    }
+
+    pub fn median(&self) -> Result<T> {
+        self.quantile(0.5)
+    }
+
+    //分位数, q 在 [0, 1] 之间, 两个元素之间线性插值 (numpy.quantile 的默认方式)
+    //用 quickselect 找出需要的两个元素, 平均 O(n), 不需要整体排序
+    pub fn quantile(&self, q: f64) -> Result<T> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(ConcurrencyError::invalid(
+                "Vector quantile",
+                format!("q {} not in [0, 1]", q),
+            ));
+        }
+        if self.is_empty() {
+            return Err(ConcurrencyError::invalid("Vector quantile", "empty vector"));
+        }
+        if self.iter().any(|v| v.partial_cmp(v).is_none()) {
+            return Err(ConcurrencyError::invalid("Vector quantile", "NaN in data"));
+        }
+        let cmp = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let h = q * (self.len() - 1) as f64;
+        let lo = h.floor() as usize;
+        let mut data = self.to_vec();
+        let (_, low, rest) = data.select_nth_unstable_by(lo, cmp);
+        let low = *low;
+        //lo + 1 位置的元素就是右边部分中最小的
+        let high = match rest.iter().min_by(|a, b| cmp(a, b)) {
+            Some(high) if h > lo as f64 => *high,
+            _ => return Ok(low),
+        };
+        Ok(low + (high - low) * T::from_f64(h - lo as f64))
+    }
+
+    //把 [min, max] 等分成 bins 个桶, 统计每个桶中元素的个数, NaN 不计入任何桶
+    //所有元素都相等时, 范围取 [v - 0.5, v + 0.5]
+    pub fn histogram(&self, bins: usize) -> Result<Histogram<T>> {
+        if bins == 0 {
+            return Err(ConcurrencyError::invalid(
+                "Vector histogram",
+                "bins must be > 0",
+            ));
+        }
+        let (mut min, mut max) = self
+            .par_min_max()?
+            .ok_or_else(|| ConcurrencyError::invalid("Vector histogram", "empty vector"))?;
+        if min == max {
+            let half = T::from_f64(0.5);
+            min = min - half;
+            max += half;
+        }
+        let width = (max - min) / T::from_usize(bins);
+        let mut edges = (0..bins)
+            .map(|i| min + width * T::from_usize(i))
+            .collect::<Vec<_>>();
+        edges.push(max);
+
+        let partials = pool::run(pool::split(self.len(), 1), |range| {
+            let mut counts = vec![0; bins];
+            for v in self[range].iter().filter(|v| v.partial_cmp(v).is_some()) {
+                //第一个大于 v 的边界的前一个桶, 等于 max 时放进最后一个桶
+                let idx = edges.partition_point(|e| e <= v).saturating_sub(1);
+                counts[idx.min(bins - 1)] += 1;
+            }
+            counts
+        })?;
+        let mut counts = vec![0; bins];
+        for partial in partials {
+            for (c, p) in counts.iter_mut().zip(partial) {
+                *c += p;
+            }
+        }
+        Ok(Histogram { edges, counts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_mean_variance() -> Result<()> {
+        let v = Vector::new([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(v.mean()?, 5.0);
+        assert!((v.variance()? - 32.0 / 7.0).abs() < 1e-12);
+        assert!((v.stddev()? - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!(Vector::<f64>::new([]).mean().is_err());
+        assert!(Vector::new([1.0]).variance().is_err());
+
+        //足够大, 会被切分到多个 worker, 合并的结果和单线程一致
+        let n = 100_000;
+        let big = Vector::new((0..n).map(|v| (v % 100) as f64 + 1e6).collect::<Vec<_>>());
+        let mut w = Welford::new();
+        big.iter().for_each(|v| w.push(*v));
+        let par = big.moments()?;
+        assert_eq!(par.count, n);
+        assert!((par.mean - w.mean).abs() < 1e-6);
+        assert!((big.variance()? - w.variance().unwrap()).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_quantile() -> Result<()> {
+        let v = Vector::new([7.0, 1.0, 3.0, 5.0]);
+        assert_eq!(v.median()?, 4.0);
+        assert_eq!(v.quantile(0.0)?, 1.0);
+        assert_eq!(v.quantile(1.0)?, 7.0);
+        assert_eq!(v.quantile(0.25)?, 2.5);
+        assert_eq!(Vector::new([3.0, 1.0, 2.0]).median()?, 2.0);
+        assert!(v.quantile(1.5).is_err());
+        assert!(Vector::new([1.0, f64::NAN]).median().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_histogram() -> Result<()> {
+        let v = Vector::new([0.0, 1.0, 1.5, 2.0, 3.0, 4.0, f64::NAN]);
+        let h = v.histogram(4)?;
+        assert_eq!(h.edges, [0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(h.counts, [1, 2, 1, 2]);
+
+        let same = Vector::new([2.0, 2.0]).histogram(2)?;
+        assert_eq!(same.edges, [1.5, 2.0, 2.5]);
+        assert_eq!(same.counts, [0, 2]);
+        assert!(v.histogram(0).is_err());
+        assert!(Vector::<f64>::new([]).histogram(3).is_err());
+
+        let n = 100_000;
+        let big = Vector::new((0..n).map(|v| v as f64).collect::<Vec<_>>());
+        let h = big.histogram(10)?;
+        assert_eq!(h.counts.iter().sum::<usize>(), n);
+        assert!(h.counts.iter().all(|c| *c == n / 10));
+        Ok(())
+    }
+}
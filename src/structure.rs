@@ -0,0 +1,243 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+use crate::{pool, ConcurrencyError, Matrix, Result, Scalar};
+
+// 按行切分, 在 pool 的 worker 中并行生成结果矩阵的每一行, f(i, out) 把第 i 行的 col 个元素 push 到 out
+pub(crate) fn fill<T, F>(row: usize, col: usize, f: F) -> Result<Matrix<T>>
+where
+    T: Send,
+    F: Fn(usize, &mut Vec<T>) + Sync,
+{
+    let chunks = pool::run(pool::split(row, col), |rows| {
+        let mut data = Vec::with_capacity(rows.len() * col);
+        for i in rows {
+            f(i, &mut data);
+        }
+        data
+    })?;
+    Ok(Matrix::new(
+        chunks.into_iter().flatten().collect::<Vec<_>>(),
+        row,
+        col,
+    ))
+}
+
+// Kronecker 积, a 是 m*n, b 是 p*q, 结果是 (m*p)*(n*q)
+// 结果中 (i*p + k, j*q + l) 位置的值为 a[i][j] * b[k][l]
+pub fn kron<T: Scalar>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>> {
+    let (p, q) = (b.row(), b.col());
+    fill(a.row() * p, a.col() * q, |r, out| {
+        let (i, k) = (r / p, r % p);
+        for j in 0..a.col() {
+            let x = a.data()[i * a.col() + j];
+            for l in 0..q {
+                out.push(x * b.data()[k * q + l]);
+            }
+        }
+    })
+}
+
+// 左右拼接, 所有矩阵的行数必须一致
+pub fn hstack<T: Copy + Send + Sync>(ms: &[&Matrix<T>]) -> Result<Matrix<T>> {
+    let Some(first) = ms.first() else {
+        return Ok(Matrix::new(vec![], 0, 0));
+    };
+    if let Some(m) = ms.iter().find(|m| m.row() != first.row()) {
+        return Err(ConcurrencyError::ShapeMismatch {
+            op: "Matrix hstack",
+            left: (first.row(), first.col()),
+            right: (m.row(), m.col()),
+        });
+    }
+    let col = ms.iter().map(|m| m.col()).sum();
+    fill(first.row(), col, |i, out| {
+        for m in ms {
+            out.extend_from_slice(&m.data()[i * m.col()..(i + 1) * m.col()]);
+        }
+    })
+}
+
+// 上下拼接, 所有矩阵的列数必须一致
+pub fn vstack<T: Copy + Send + Sync>(ms: &[&Matrix<T>]) -> Result<Matrix<T>> {
+    let Some(first) = ms.first() else {
+        return Ok(Matrix::new(vec![], 0, 0));
+    };
+    if let Some(m) = ms.iter().find(|m| m.col() != first.col()) {
+        return Err(ConcurrencyError::ShapeMismatch {
+            op: "Matrix vstack",
+            left: (first.row(), first.col()),
+            right: (m.row(), m.col()),
+        });
+    }
+    //结果的第 i 行来自第几个矩阵的第几行
+    let mut rows = Vec::new();
+    for m in ms {
+        rows.extend((0..m.row()).map(|i| (*m, i)));
+    }
+    let col = first.col();
+    fill(rows.len(), col, |r, out| {
+        let (m, i) = rows[r];
+        out.extend_from_slice(&m.data()[i * col..(i + 1) * col]);
+    })
+}
+
+// 分块拼接, block(&[[&a, &b], [&c, &d]]) 得到
+// | a b |
+// | c d |
+// 每一行块先 hstack, 再把各行 vstack
+pub fn block<'a, T, R>(blocks: &[R]) -> Result<Matrix<T>>
+where
+    T: Copy + Send + Sync + 'a,
+    R: AsRef<[&'a Matrix<T>]>,
+{
+    let rows = blocks
+        .iter()
+        .map(|r| hstack(r.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    vstack(&rows.iter().collect::<Vec<_>>())
+}
+
+impl<T: Copy + Send + Sync> Matrix<T> {
+    //按行优先的顺序改变形状, 元素个数必须一致
+    pub fn reshape(&self, row: usize, col: usize) -> Result<Matrix<T>> {
+        if row * col != self.data().len() {
+            return Err(ConcurrencyError::ShapeMismatch {
+                op: "Matrix reshape",
+                left: (self.row(), self.col()),
+                right: (row, col),
+            });
+        }
+        Ok(Matrix::new(self.data().to_vec(), row, col))
+    }
+
+    //取出子矩阵, 比如 a.slice(1..3, ..) 取第 1, 2 行的所有列
+    pub fn slice(
+        &self,
+        rows: impl RangeBounds<usize>,
+        cols: impl RangeBounds<usize>,
+    ) -> Result<Matrix<T>> {
+        let rows = to_range(rows, self.row(), "Matrix slice rows")?;
+        let cols = to_range(cols, self.col(), "Matrix slice cols")?;
+        fill(rows.len(), cols.len(), |i, out| {
+            let start = (rows.start + i) * self.col();
+            out.extend_from_slice(&self.data()[start + cols.start..start + cols.end]);
+        })
+    }
+
+    //切分成 block_row * block_col 大小的块, 按行优先返回块的网格
+    //不能整除时, 最后一行/列的块会小一些, block(&blocks) 可以还原出原矩阵
+    pub fn split_into_blocks(
+        &self,
+        block_row: usize,
+        block_col: usize,
+    ) -> Result<Vec<Vec<Matrix<T>>>> {
+        if block_row == 0 || block_col == 0 {
+            return Err(ConcurrencyError::invalid(
+                "Matrix split",
+                "block size must be positive",
+            ));
+        }
+        (0..self.row())
+            .step_by(block_row)
+            .map(|i| {
+                (0..self.col())
+                    .step_by(block_col)
+                    .map(|j| {
+                        self.slice(
+                            i..i + block_row.min(self.row() - i),
+                            j..j + block_col.min(self.col() - j),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn to_range(r: impl RangeBounds<usize>, len: usize, op: &'static str) -> Result<Range<usize>> {
+    let start = match r.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match r.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    if end > len {
+        return Err(ConcurrencyError::OutOfBounds {
+            op,
+            index: end,
+            len,
+        });
+    }
+    if start > end {
+        return Err(ConcurrencyError::invalid(
+            op,
+            format!("range {}..{} is reversed", start, end),
+        ));
+    }
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kron() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, 4], 2, 2);
+        let b = Matrix::new([0, 5, 6, 7], 2, 2);
+        let c = kron(&a, &b)?;
+        assert_eq!((c.row(), c.col()), (4, 4));
+        assert_eq!(
+            c.data(),
+            [0, 5, 0, 10, 6, 7, 12, 14, 0, 15, 0, 20, 18, 21, 24, 28]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stack_and_block() -> Result<()> {
+        let a = Matrix::new([1, 2, 3, 4], 2, 2);
+        let b = Matrix::new([5, 6], 2, 1);
+        let c = Matrix::new([7, 8], 1, 2);
+        let d = Matrix::new([9], 1, 1);
+
+        let h = hstack(&[&a, &b])?;
+        assert_eq!(h.data(), [1, 2, 5, 3, 4, 6]);
+        let v = vstack(&[&a, &c])?;
+        assert_eq!(v.data(), [1, 2, 3, 4, 7, 8]);
+        assert!(hstack(&[&a, &c]).is_err());
+        assert!(vstack(&[&a, &b]).is_err());
+
+        let m = block(&[[&a, &b], [&c, &d]])?;
+        assert_eq!((m.row(), m.col()), (3, 3));
+        assert_eq!(m.data(), [1, 2, 5, 3, 4, 6, 7, 8, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reshape_slice_split() -> Result<()> {
+        let a = Matrix::new((1..=12).collect::<Vec<_>>(), 3, 4);
+        assert_eq!(a.reshape(4, 3)?.col(), 3);
+        assert!(a.reshape(5, 3).is_err());
+
+        let s = a.slice(1..3, 1..=2)?;
+        assert_eq!(s.data(), [6, 7, 10, 11]);
+        assert_eq!(a.slice(.., 3..)?.data(), [4, 8, 12]);
+        assert!(a.slice(0..4, ..).is_err());
+
+        let blocks = a.split_into_blocks(2, 3)?;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0][1].data(), [4, 8]);
+        assert_eq!(blocks[1][0].data(), [9, 10, 11]);
+        let rows = blocks
+            .iter()
+            .map(|r| r.iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(block(&rows)?.data(), a.data());
+        Ok(())
+    }
+}
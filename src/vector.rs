@@ -1,8 +1,8 @@
-use std::ops::{Add, AddAssign, Deref, Mul};
+use std::ops::{Add, Deref, Mul, Neg, Sub};
 
-use anyhow::anyhow;
-use anyhow::Result;
+use crate::{pool, Accumulator, ConcurrencyError, Plain, Real, Result, Scalar};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Vector<T> {
     data: Vec<T>,
 }
@@ -32,20 +32,712 @@ impl<T> Vector<T> {
     //     }s
 }
 
+impl<T> From<Vec<T>> for Vector<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self { data }
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for Vector<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Vector<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl<T> AsRef<[T]> for Vector<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.data
+    }
+}
+
+// 借用的向量视图, 不复制数据
+// stride 为 1 时就是一段连续的切片, 否则每隔 stride 个元素取一个, 比如矩阵的一列
+#[derive(Debug)]
+pub struct VectorView<'a, T> {
+    data: &'a [T],
+    stride: usize,
+    len: usize,
+}
+
+// derive 会要求 T: Copy, 视图本身只是引用, 总是可以复制
+impl<T> Clone for VectorView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for VectorView<'_, T> {}
+
+impl<'a, T> VectorView<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        Self {
+            data,
+            stride: 1,
+            len: data.len(),
+        }
+    }
+
+    //从 offset 开始每隔 stride 取一个, 共 len 个, 越界时返回错误
+    pub fn strided(data: &'a [T], offset: usize, stride: usize, len: usize) -> Result<Self> {
+        if stride == 0 {
+            return Err(ConcurrencyError::invalid(
+                "VectorView",
+                "stride must be > 0",
+            ));
+        }
+        //长度为 0 的视图不读取任何元素, offset 在哪里都可以, 比如 0 行矩阵的某一列
+        if len == 0 {
+            return Ok(Self {
+                data: &[],
+                stride,
+                len,
+            });
+        }
+        let end = offset + (len - 1) * stride + 1;
+        if end > data.len() {
+            return Err(ConcurrencyError::OutOfBounds {
+                op: "VectorView",
+                index: end - 1,
+                len: data.len(),
+            });
+        }
+        Ok(Self {
+            data: &data[offset..end],
+            stride,
+            len,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'a T> {
+        if i < self.len {
+            Some(&self.data[i * self.stride])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.data.iter().step_by(self.stride)
+    }
+
+    //在 mid 处分成两个视图, 和 slice::split_at 一样, mid > len 时 panic
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "VectorView split_at: mid > len");
+        let (left, right) = self.data.split_at((mid * self.stride).min(self.data.len()));
+        (
+            Self {
+                data: left,
+                stride: self.stride,
+                len: mid,
+            },
+            Self {
+                data: right,
+                stride: self.stride,
+                len: self.len - mid,
+            },
+        )
+    }
+
+    //连续的视图直接返回切片, 有 stride 时返回 None
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        if self.stride == 1 {
+            Some(self.data)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_vector(&self) -> Vector<T>
+    where
+        T: Copy,
+    {
+        Vector::new(self.iter().copied().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Vector<T> {
+    pub fn view(&self) -> VectorView<'_, T> {
+        VectorView::new(&self.data)
+    }
+}
+
+// dot_product 可以接受的参数: Vector, VectorView, Vec, 数组, 切片, 以及它们的引用
+pub trait AsVectorView<T> {
+    fn as_view(&self) -> VectorView<'_, T>;
+}
+
+impl<T, S: AsRef<[T]> + ?Sized> AsVectorView<T> for S {
+    fn as_view(&self) -> VectorView<'_, T> {
+        VectorView::new(self.as_ref())
+    }
+}
+
+impl<T> AsVectorView<T> for VectorView<'_, T> {
+    fn as_view(&self) -> VectorView<'_, T> {
+        *self
+    }
+}
+
 //点乘方法, 相同长度的数字,相同位置相乘的结果进行累加, 最后返回累加值
 // 这里对 入参进行封装, 自定义Vector类型
-pub fn dot_product<T>(a: Vector<T>, b: Vector<T>) -> Result<T>
+pub fn dot_product<T: Scalar>(a: impl AsVectorView<T>, b: impl AsVectorView<T>) -> Result<T> {
+    dot_product_with(a, b, Plain)
+}
+
+// 指定累加策略的点乘, 比如 Checked 在整数溢出时返回错误, Widening 把 i32 累加到 i64
+// 带 stride 的视图直接交给累加策略按 stride 读取, 不会复制
+pub fn dot_product_with<T, A>(
+    a: impl AsVectorView<T>,
+    b: impl AsVectorView<T>,
+    acc: A,
+) -> Result<A::Output>
 where
-    T: Copy + Default + Add<Output = T> + AddAssign + Mul<Output = T>,
+    T: Copy,
+    A: Accumulator<T>,
 {
-    if a.len() != b.len() {
-        return Err(anyhow!("Dot product error: a.len != b.len"));
+    let (a, b) = (a.as_view(), b.as_view());
+    check_len(&a, &b, "Dot product")?;
+    acc.dot_view(a, b)
+}
+
+// 多线程点乘, 用于非常长的向量(千万级别)
+// 按 pool::split 把两个向量切成连续的块, 每个 worker 计算一块的部分和, 再按块的顺序累加
+// 切分只和长度有关, 和线程调度无关, 所以浮点数的累加顺序是固定的, 结果可以复现
+// 注意结果和单线程的 dot_product 不一定完全相等, 因为累加顺序不同
+pub fn par_dot_product<T: Scalar>(a: impl AsVectorView<T>, b: impl AsVectorView<T>) -> Result<T> {
+    let (a, b) = (a.as_view(), b.as_view());
+    check_len(&a, &b, "Dot product")?;
+    let partials = pool::run(pool::split(a.len(), 1), |range| {
+        let (a, b) = (a.split_at(range.start).1, b.split_at(range.start).1);
+        Plain.dot_view(a.split_at(range.len()).0, b.split_at(range.len()).0)
+    })?;
+    let mut sum = T::zero();
+    for partial in partials {
+        sum += partial?;
     }
+    Ok(sum)
+}
+
+// 借用版本的点乘, 调用之后 a 和 b 还可以继续使用
+pub fn dot<T: Scalar>(a: &impl AsVectorView<T>, b: &impl AsVectorView<T>) -> Result<T> {
+    dot_product_with(a.as_view(), b.as_view(), Plain)
+}
 
-    let mut sum = T::default();
-    for i in 0..a.len() {
-        sum += a[i] * b[i];
+// 迭代器版本的点乘, 不需要先 collect 成 Vector
+// 两个迭代器必须同时结束, 有一边先结束时立即返回错误, 不会继续读取较长的一边 (可能是无限的)
+// 所以错误中较长一边的长度只是目前读到的个数, 比如 left: 2, right: 3 表示 a 有 2 个, b 至少有 3 个
+pub fn dot_iter<T: Scalar>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+) -> Result<T> {
+    let (mut a, mut b) = (a.into_iter(), b.into_iter());
+    let mut sum = T::zero();
+    let mut n = 0;
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => sum += x * y,
+            (None, None) => return Ok(sum),
+            (None, Some(_)) => {
+                return Err(ConcurrencyError::LengthMismatch {
+                    op: "Dot product",
+                    left: n,
+                    right: n + 1,
+                })
+            }
+            (Some(_), None) => {
+                return Err(ConcurrencyError::LengthMismatch {
+                    op: "Dot product",
+                    left: n + 1,
+                    right: n,
+                })
+            }
+        }
+        n += 1;
     }
+}
 
-    Ok(sum)
+// 元素类型和结果类型不同的点乘, 每个元素先转换成 O 再相乘累加
+// 比如 i32 · i32 -> i64, u8 · u16 -> u32, 避免在窄类型上溢出
+// let r: i64 = dot_as(&a, &b)?;
+pub fn dot_as<O, T, U>(a: &impl AsVectorView<T>, b: &impl AsVectorView<U>) -> Result<O>
+where
+    O: Scalar,
+    T: Copy + Into<O>,
+    U: Copy + Into<O>,
+{
+    let (a, b) = (a.as_view(), b.as_view());
+    dot_iter(a.iter().map(|v| (*v).into()), b.iter().map(|v| (*v).into()))
+}
+
+// 向量之间的距离
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance {
+    // 曼哈顿距离 sum(|a - b|)
+    L1,
+    // 欧氏距离 sqrt(sum((a - b)^2))
+    L2,
+    // 余弦距离 1 - cosine_similarity(a, b)
+    Cosine,
+}
+
+pub(crate) fn check_len<T>(
+    a: &impl AsVectorView<T>,
+    b: &impl AsVectorView<T>,
+    op: &'static str,
+) -> Result<()> {
+    let (a, b) = (a.as_view().len(), b.as_view().len());
+    if a != b {
+        return Err(ConcurrencyError::LengthMismatch {
+            op,
+            left: a,
+            right: b,
+        });
+    }
+    Ok(())
+}
+
+// 按元素运算的版本, 长度不一致时返回错误
+// 对应的 + - 运算符在长度不一致时会 panic, 和 Matrix 的 * 一样
+impl<T: Scalar> Vector<T> {
+    pub fn try_add(&self, rhs: &Vector<T>) -> Result<Vector<T>> {
+        check_len(self, rhs, "Vector add")?;
+        Ok(self.zip_map(rhs, |a, b| a + b))
+    }
+
+    pub fn try_sub(&self, rhs: &Vector<T>) -> Result<Vector<T>>
+    where
+        T: Sub<Output = T>,
+    {
+        check_len(self, rhs, "Vector sub")?;
+        Ok(self.zip_map(rhs, |a, b| a - b))
+    }
+
+    //乘以标量
+    pub fn scale(&self, k: T) -> Vector<T> {
+        Vector::new(self.iter().map(|v| *v * k).collect::<Vec<_>>())
+    }
+
+    //self = a * x + self, BLAS 中的 axpy, 原地修改避免分配新的向量
+    pub fn axpy(&mut self, a: T, x: &Vector<T>) -> Result<()> {
+        check_len(self, x, "Vector axpy")?;
+        for (y, x) in self.data.iter_mut().zip(x.iter()) {
+            *y += a * *x;
+        }
+        Ok(())
+    }
+
+    //三维向量的叉乘, 长度不是 3 时返回错误
+    pub fn cross(&self, rhs: &Vector<T>) -> Result<Vector<T>>
+    where
+        T: Sub<Output = T>,
+    {
+        if self.len() != 3 || rhs.len() != 3 {
+            return Err(ConcurrencyError::invalid(
+                "Vector cross",
+                format!("expected 3-D vectors, got {} and {}", self.len(), rhs.len()),
+            ));
+        }
+        let (a, b) = (&self.data, &rhs.data);
+        Ok(Vector::new([
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]))
+    }
+
+    fn zip_map(&self, rhs: &Vector<T>, f: impl Fn(T, T) -> T) -> Vector<T> {
+        Vector::new(
+            self.iter()
+                .zip(rhs.iter())
+                .map(|(a, b)| f(*a, *b))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl<T: Real> Vector<T> {
+    //L2 范数 sqrt(sum(x^2))
+    pub fn norm(&self) -> T {
+        Plain.dot(self, self).unwrap_or(T::zero()).sqrt()
+    }
+
+    //L1 范数 sum(|x|)
+    pub fn norm_l1(&self) -> T {
+        self.iter().fold(T::zero(), |acc, v| acc + v.abs())
+    }
+
+    //无穷范数 max(|x|), 空向量为 0
+    pub fn norm_inf(&self) -> T {
+        self.iter().fold(
+            T::zero(),
+            |acc, v| if v.abs() > acc { v.abs() } else { acc },
+        )
+    }
+
+    //单位向量, 零向量无法归一化, 返回错误
+    pub fn normalize(&self) -> Result<Vector<T>> {
+        let norm = self.norm();
+        if norm.is_zero() {
+            return Err(ConcurrencyError::invalid("Vector normalize", "zero vector"));
+        }
+        Ok(self.scale(T::one() / norm))
+    }
+
+    //余弦相似度 a.b / (|a| |b|), 长度不一致或者有零向量时返回错误
+    pub fn cosine_similarity(&self, rhs: &Vector<T>) -> Result<T> {
+        check_len(self, rhs, "Vector cosine similarity")?;
+        let norms = self.norm() * rhs.norm();
+        if norms.is_zero() {
+            return Err(ConcurrencyError::invalid(
+                "Vector cosine similarity",
+                "zero vector",
+            ));
+        }
+        Ok(Plain.dot(self, rhs)? / norms)
+    }
+
+    pub fn distance(&self, rhs: &Vector<T>, distance: Distance) -> Result<T> {
+        check_len(self, rhs, "Vector distance")?;
+        match distance {
+            Distance::L1 => Ok(self.try_sub(rhs)?.norm_l1()),
+            Distance::L2 => Ok(self.try_sub(rhs)?.norm()),
+            Distance::Cosine => Ok(T::one() - self.cosine_similarity(rhs)?),
+        }
+    }
+}
+
+impl<T: Scalar> Add for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, rhs: Self) -> Vector<T> {
+        self.try_add(rhs).expect("Vector add error")
+    }
+}
+
+impl<T: Scalar> Add for Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, rhs: Self) -> Vector<T> {
+        &self + &rhs
+    }
+}
+
+impl<T: Scalar + Sub<Output = T>> Sub for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Vector<T> {
+        self.try_sub(rhs).expect("Vector sub error")
+    }
+}
+
+impl<T: Scalar + Sub<Output = T>> Sub for Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Vector<T> {
+        &self - &rhs
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Neg for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        Vector::new(self.iter().map(|v| -*v).collect::<Vec<_>>())
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        -&self
+    }
+}
+
+//向量乘以标量, v * 2
+impl<T: Scalar> Mul<T> for &Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, k: T) -> Vector<T> {
+        self.scale(k)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, k: T) -> Vector<T> {
+        self.scale(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Checked, Fma, Kahan, Neumaier, Pairwise, Saturating, Widening, Wrapping};
+
+    #[test]
+    fn test_dot_product_overflow_policies() -> Result<()> {
+        let a = || Vector::new([i32::MAX, 2]);
+        let b = || Vector::new([2, 3]);
+        let err = dot_product_with(a(), b(), Checked).unwrap_err();
+        assert_eq!(err.to_string(), "Dot product overflow at index 0");
+        assert_eq!(dot_product_with(a(), b(), Wrapping)?, 4);
+        assert_eq!(dot_product_with(a(), b(), Saturating)?, i32::MAX);
+        assert_eq!(
+            dot_product_with(a(), b(), Widening)?,
+            i32::MAX as i64 * 2 + 6
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dot_product_float_accumulators() -> Result<()> {
+        // 1.0 后面跟 100 万个 1e-8, 朴素累加时小数全部被吞掉
+        let n = 1_000_000;
+        let mut data = vec![1e-8f32; n + 1];
+        data[0] = 1.0;
+        let a = || Vector::new(data.clone());
+        let b = || Vector::new(vec![1.0f32; n + 1]);
+        let expected = 1.01f32;
+
+        assert_eq!(dot_product(a(), b())?, 1.0);
+        assert_eq!(dot_product_with(a(), b(), Fma)?, 1.0);
+        assert!((dot_product_with(a(), b(), Kahan)? - expected).abs() < 1e-6);
+        assert!((dot_product_with(a(), b(), Pairwise)? - expected).abs() < 1e-6);
+
+        // 加入的项比累加值大时, Kahan 会丢掉补偿, Neumaier 不会
+        let a = || Vector::new([1.0f64, 1e100, 1.0, -1e100]);
+        let b = || Vector::new([1.0f64; 4]);
+        assert_eq!(dot_product_with(a(), b(), Kahan)?, 0.0);
+        assert_eq!(dot_product_with(a(), b(), Neumaier)?, 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_accumulator_length_mismatch() {
+        // 直接调用累加策略时长度不一致也返回错误, 而不是只算较短的部分
+        fn mismatch<O>(op: &'static str, left: usize, right: usize) -> Result<O> {
+            Err(ConcurrencyError::LengthMismatch { op, left, right })
+        }
+        let (a, b) = ([1.0, 2.0, 3.0], [1.0]);
+        assert_eq!(Plain.dot(&a, &b), mismatch("Plain dot", 3, 1));
+        assert_eq!(Kahan.dot(&a, &b), mismatch("Kahan dot", 3, 1));
+        assert_eq!(Neumaier.dot(&a, &b), mismatch("Neumaier dot", 3, 1));
+        assert_eq!(Fma.dot(&a, &b), mismatch("Fma dot", 3, 1));
+        // Pairwise 在拆分之前检查, 不会 panic
+        assert_eq!(
+            Pairwise.dot(&[1.0; 20], &[1.0; 3]),
+            mismatch("Pairwise dot", 20, 3)
+        );
+
+        let (a, b) = ([1, 2, 3], [1]);
+        assert_eq!(Checked.dot(&a, &b), mismatch("Checked dot", 3, 1));
+        assert_eq!(Wrapping.dot(&a, &b), mismatch("Wrapping dot", 3, 1));
+        assert_eq!(Saturating.dot(&b, &a), mismatch("Saturating dot", 1, 3));
+        assert_eq!(Widening.dot(&a, &b), mismatch("Widening dot", 3, 1));
+    }
+
+    #[test]
+    fn test_dot_by_reference_and_iterators() -> Result<()> {
+        let a = Vector::new([1, 2, 3]);
+        let b = Vector::new([4, 5, 6]);
+        assert_eq!(dot(&a, &b)?, 32);
+        // a 和 b 没有被 move, 可以继续使用
+        assert_eq!(dot(&a, &a)?, 14);
+        assert_eq!(dot(&a.view(), &[1, 1, 1])?, 6);
+
+        assert_eq!(dot_iter(a.iter().copied(), 4..7)?, 32);
+        let err = dot_iter(1..3, 1..5).unwrap_err();
+        assert_eq!(
+            err,
+            ConcurrencyError::LengthMismatch {
+                op: "Dot product",
+                left: 2,
+                right: 3
+            }
+        );
+        assert_eq!(err.to_string(), "Dot product error: length 2 != 3");
+        assert!(dot_iter(1..4, 1..3).is_err());
+        // 较长的一边是无限的迭代器也会立即返回
+        assert_eq!(
+            dot_iter(std::iter::repeat(1), 1..3),
+            Err(ConcurrencyError::LengthMismatch {
+                op: "Dot product",
+                left: 3,
+                right: 2
+            })
+        );
+
+        let big = Vector::new([i32::MAX, i32::MAX]);
+        let r: i64 = dot_as(&big, &[2i32, 2])?;
+        assert_eq!(r, i32::MAX as i64 * 4);
+        assert_eq!(dot_as::<u32, u8, u16>(&[255u8], &[1000u16])?, 255_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_par_dot_product() -> Result<()> {
+        //足够大, 会被切分到多个 worker
+        let n = 100_000;
+        let a = Vector::new((0..n).map(|v| v as u64).collect::<Vec<_>>());
+        let b = Vector::new(vec![2u64; n]);
+        assert_eq!(par_dot_product(&a, &b)?, dot(&a, &b)?);
+        assert!(par_dot_product(&a, [1u64]).is_err());
+
+        // 浮点数的结果每次都一样
+        let x = Vector::new((0..n).map(|v| 1.0 / (v as f64 + 1.0)).collect::<Vec<_>>());
+        let first = par_dot_product(&x, &x)?;
+        for _ in 0..5 {
+            assert_eq!(par_dot_product(&x, &x)?.to_bits(), first.to_bits());
+        }
+        assert!((first - dot(&x, &x)?).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_view() -> Result<()> {
+        // 2 * 3 矩阵 [[1, 2, 3], [4, 5, 6]] 的第 1 列
+        let data = [1, 2, 3, 4, 5, 6];
+        let col = VectorView::strided(&data, 1, 3, 2)?;
+        assert_eq!(col.len(), 2);
+        assert_eq!(col.iter().copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(col.get(1), Some(&5));
+        assert_eq!(col.get(2), None);
+        assert!(col.as_slice().is_none());
+        assert!(VectorView::strided(&data, 1, 3, 3).is_err());
+        assert!(VectorView::strided(&data, 0, 0, 1).is_err());
+
+        let row = VectorView::new(&data[..2]);
+        assert_eq!(dot_product(row, col)?, 12);
+        let v = Vector::new([1, 1]);
+        assert_eq!(dot_product(v.view(), [3, 4])?, 7);
+        assert_eq!(dot_product(&v, vec![3, 4])?, 7);
+        assert!(VectorView::strided(&data, 10, 3, 0)?.is_empty());
+
+        let (left, right) = col.split_at(1);
+        assert_eq!(left.iter().copied().collect::<Vec<_>>(), [2]);
+        assert_eq!(right.iter().copied().collect::<Vec<_>>(), [5]);
+        assert!(col.split_at(2).1.is_empty());
+
+        // 累加策略直接按 stride 读取, 结果和复制成连续的一样
+        let data = (0..3000).map(|v| v as f64 / 7.0).collect::<Vec<_>>();
+        let col = VectorView::strided(&data, 2, 3, 1000)?;
+        let dense = col.to_vector();
+        for acc in [
+            &crate::Pairwise as &dyn Accumulator<f64, Output = f64>,
+            &crate::Kahan,
+        ] {
+            assert_eq!(acc.dot_view(col, col)?, acc.dot(&dense, &dense)?);
+        }
+        let simd = crate::Simd.dot_view(col, col)?;
+        assert!((simd - Plain.dot(&dense, &dense)?).abs() < 1e-6);
+        assert!(crate::Simd.dot_view(col, col.split_at(1).0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_arithmetic() -> Result<()> {
+        let a = Vector::new([1, 2, 3]);
+        let b = Vector::new([4, 5, 6]);
+        assert_eq!(*(&a + &b), [5, 7, 9]);
+        assert_eq!(*(&b - &a), [3, 3, 3]);
+        assert_eq!(*(-&a), [-1, -2, -3]);
+        assert_eq!(*(&a * 2), [2, 4, 6]);
+        assert_eq!(*a.cross(&b)?, [-3, 6, -3]);
+        assert!(a.try_add(&Vector::new([1])).is_err());
+        assert!(Vector::new([1, 2]).cross(&Vector::new([3, 4])).is_err());
+
+        let mut y = Vector::new([1, 1, 1]);
+        y.axpy(2, &a)?;
+        assert_eq!(*y, [3, 5, 7]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vector_add_should_panic() {
+        let _ = Vector::new([1, 2]) + Vector::new([1]);
+    }
+
+    #[test]
+    fn test_vector_norms_and_distances() -> Result<()> {
+        let a = Vector::new([3.0, -4.0]);
+        assert_eq!(a.norm(), 5.0);
+        assert_eq!(a.norm_l1(), 7.0);
+        assert_eq!(a.norm_inf(), 4.0);
+        let unit = a.normalize()?;
+        assert!((unit[0] - 0.6).abs() < 1e-12 && (unit[1] + 0.8).abs() < 1e-12);
+        assert!(Vector::new([0.0, 0.0]).normalize().is_err());
+
+        let b = Vector::new([0.0, 0.0]);
+        assert_eq!(a.distance(&b, Distance::L1)?, 7.0);
+        assert_eq!(a.distance(&b, Distance::L2)?, 5.0);
+        assert!(a.distance(&b, Distance::Cosine).is_err());
+
+        let c = Vector::new([6.0, -8.0]);
+        assert!((a.cosine_similarity(&c)? - 1.0).abs() < 1e-12);
+        assert!(a.distance(&c, Distance::Cosine)?.abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vector_collection_traits() {
+        let a: Vector<i32> = (1..=3).collect();
+        let b = Vector::from(vec![1, 2, 3]);
+        assert_eq!(a, b);
+        assert_eq!(a.clone(), Vector::new([1, 2, 3]));
+        assert_ne!(a, Vector::new([1, 2]));
+
+        let mut c = a.clone();
+        c.extend([4, 5]);
+        c.extend(&[6]);
+        assert_eq!(c, (1..=6).collect());
+        assert_eq!((&c).into_iter().sum::<i32>(), 21);
+        let doubled: Vector<i32> = c.into_iter().map(|v| v * 2).collect();
+        assert_eq!(doubled[5], 12);
+
+        let set = std::collections::HashSet::from([a, b]);
+        assert_eq!(set.len(), 1);
+    }
 }